@@ -0,0 +1,319 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use letter::Letter;
+use polynomial::{cyclotomic_polynomial, Polynomial};
+
+fn hooks<L>(weight: &[L]) -> Vec<usize>
+	where L: Letter
+{
+	let mut hooks = Vec::new();
+
+	for (row_index, row) in weight.iter().enumerate() {
+		for column in 0..row.to_usize() {
+			let height = weight[row_index..]
+				.iter()
+				.take_while(|other_row| other_row.to_usize() > column)
+				.count();
+			hooks.push(row.to_usize() - column + height - 1);
+		}
+	}
+
+	hooks
+}
+
+// The cells of the shape as (row, column) pairs.
+fn cells<L>(weight: &[L]) -> Vec<(usize, usize)>
+	where L: Letter
+{
+	let mut cells = Vec::new();
+
+	for (row_index, row) in weight.iter().enumerate() {
+		for column in 0..row.to_usize() {
+			cells.push((row_index, column));
+		}
+	}
+
+	cells
+}
+
+// Pushes signed multiplicities of the integers down onto the primes so that a product of integers
+// divided by another can be multiplied out without intermediate division.
+fn push_to_primes(exponents: &mut [isize]) {
+	let n = exponents.len().saturating_sub(1);
+
+	let mut smallest_factor = vec![0; n + 1];
+	for k in 2..n + 1 {
+		if smallest_factor[k] == 0 {
+			let mut multiple = k;
+			while multiple <= n {
+				if smallest_factor[multiple] == 0 {
+					smallest_factor[multiple] = k;
+				}
+				multiple += k;
+			}
+		}
+	}
+
+	for k in (2..n + 1).rev() {
+		let factor = smallest_factor[k];
+		if factor != k {
+			let exponent = exponents[k];
+			exponents[k] = 0;
+			exponents[factor] += exponent;
+			exponents[k / factor] += exponent;
+		}
+	}
+}
+
+// The hook length formula as a signed multiplicity for every integer up to n.
+fn hook_length_exponents<L>(weight: &[L]) -> Vec<isize>
+	where L: Letter
+{
+	let n = weight.iter().fold(0, |partial, row| partial + row.to_usize());
+
+	let mut exponents = vec![0; n + 1];
+	for k in 2..n + 1 {
+		exponents[k] += 1;
+	}
+
+	for hook in hooks(weight) {
+		exponents[hook] -= 1;
+	}
+
+	push_to_primes(&mut *exponents);
+	exponents
+}
+
+// The hook content formula `Π (k + c(u)) / Π h(u)` for entries in `0..k` as a signed multiplicity
+// for every integer, or `None` if some factor `k + c(u)` vanishes.
+fn hook_content_exponents<L>(weight: &[L], entries: usize) -> Option<Vec<isize>>
+	where L: Letter
+{
+	if weight.iter().filter(|row| row.to_usize() != 0).count() > entries {
+		return None;
+	}
+
+	let largest = entries + weight.first().map(|row| row.to_usize()).unwrap_or(0);
+
+	let mut exponents = vec![0; largest + 1];
+	for (row, column) in cells(weight) {
+		exponents[entries + column - row] += 1;
+	}
+
+	for hook in hooks(weight) {
+		exponents[hook] -= 1;
+	}
+
+	exponents[1] = 0;
+	push_to_primes(&mut *exponents);
+	Some(exponents)
+}
+
+fn checked_product(exponents: Vec<isize>) -> Option<usize> {
+	let mut count: usize = 1;
+
+	for (factor, exponent) in exponents.into_iter().enumerate().skip(2) {
+		debug_assert!(exponent >= 0);
+		for _ in 0..exponent {
+			count = count.checked_mul(factor)?;
+		}
+	}
+
+	Some(count)
+}
+
+fn big_product(exponents: Vec<isize>) -> BigUint {
+	let mut count = BigUint::one();
+
+	for (factor, exponent) in exponents.into_iter().enumerate().skip(2) {
+		debug_assert!(exponent >= 0);
+		for _ in 0..exponent {
+			count = count * BigUint::from(factor as u64);
+		}
+	}
+
+	count
+}
+
+/// The number of standard Young tableaux of the given shape, or `None` if it overflows.
+#[inline]
+pub(crate) fn hook_length_count<L>(weight: &[L]) -> Option<usize>
+	where L: Letter
+{
+	checked_product(hook_length_exponents(weight))
+}
+
+/// The number of standard Young tableaux of the given shape.
+#[inline]
+pub(crate) fn hook_length_big_count<L>(weight: &[L]) -> BigUint
+	where L: Letter
+{
+	big_product(hook_length_exponents(weight))
+}
+
+/// The number of semistandard Young tableaux of the given shape with entries in `0..entries`, or
+/// `None` if it overflows.
+#[inline]
+pub(crate) fn hook_content_count<L>(weight: &[L], entries: usize) -> Option<usize>
+	where L: Letter
+{
+	match hook_content_exponents(weight, entries) {
+		Some(exponents) => checked_product(exponents),
+		None => Some(0),
+	}
+}
+
+/// The number of semistandard Young tableaux of the given shape with entries in `0..entries`.
+#[inline]
+pub(crate) fn hook_content_big_count<L>(weight: &[L], entries: usize) -> BigUint
+	where L: Letter
+{
+	match hook_content_exponents(weight, entries) {
+		Some(exponents) => big_product(exponents),
+		None => BigUint::from(0u64),
+	}
+}
+
+// Since [k]_q is the product of the cyclotomic polynomials Φ_d for the divisors 1 < d | k, the
+// q-analogues of the hook length and hook content formulas are products of the Φ_d raised to the
+// number of numerator factors divisible by d minus the number of hooks divisible by d.
+fn q_exponents<I, L>(numerators: I, weight: &[L]) -> Vec<isize>
+	where I: IntoIterator<Item = usize>,
+	      L: Letter
+{
+	let hooks = hooks(weight);
+	let numerators: Vec<_> = numerators.into_iter().collect();
+	let largest = numerators.iter().chain(hooks.iter()).cloned().max().unwrap_or(0);
+
+	let mut exponents = vec![0; largest + 1];
+	for factor in numerators {
+		for d in (2..factor + 1).filter(|d| factor % d == 0) {
+			exponents[d] += 1;
+		}
+	}
+
+	for hook in hooks {
+		for d in (2..hook + 1).filter(|d| hook % d == 0) {
+			exponents[d] -= 1;
+		}
+	}
+
+	exponents
+}
+
+// q^b(λ) times the product of the cyclotomic polynomials with the given exponents, where
+// `b(λ) = Σ (i - 1) λ_i`
+fn cyclotomic_product<L>(weight: &[L], exponents: Vec<isize>) -> Polynomial
+	where L: Letter
+{
+	let offset = weight
+		.iter()
+		.enumerate()
+		.fold(0, |partial, (index, row)| partial + index * row.to_usize());
+
+	let mut polynomial = Polynomial::monomial(&[offset], 1);
+
+	for (d, exponent) in exponents.into_iter().enumerate().skip(2) {
+		debug_assert!(exponent >= 0);
+		if exponent == 0 {
+			continue;
+		}
+
+		let mut cyclotomic = Polynomial::zero();
+		for (exponent, coefficient) in cyclotomic_polynomial(d).into_iter().enumerate() {
+			cyclotomic.add_term(&[exponent], coefficient);
+		}
+
+		for _ in 0..exponent {
+			polynomial = &polynomial * &cyclotomic;
+		}
+	}
+
+	polynomial
+}
+
+/// The major index generating function `q^b(λ) Π [k]_q / Π [h]_q` of standard Young tableaux of the
+/// given shape, where `b(λ) = Σ (i - 1) λ_i`, or `None` if a coefficient would overflow.
+pub(crate) fn q_hook_length_polynomial<L>(weight: &[L]) -> Option<Polynomial>
+	where L: Letter
+{
+	if hook_length_count(weight).map(|count| count > i64::max_value() as usize).unwrap_or(true) {
+		return None;
+	}
+
+	let n = weight.iter().fold(0, |partial, row| partial + row.to_usize());
+	Some(cyclotomic_product(weight, q_exponents(1..n + 1, weight)))
+}
+
+/// The principal specialization `s_λ(1, q, ..., q^(k - 1)) = q^b(λ) Π [k + c(u)]_q / Π [h(u)]_q`,
+/// i.e. the generating function of semistandard tableaux with entries in `0..k` by the sum of their
+/// entries, or `None` if a coefficient would overflow.
+pub(crate) fn q_hook_content_polynomial<L>(weight: &[L], entries: usize) -> Option<Polynomial>
+	where L: Letter
+{
+	match hook_content_count(weight, entries) {
+		Some(0) => return Some(Polynomial::zero()),
+		Some(count) if count <= i64::max_value() as usize => {},
+		_ => return None,
+	}
+
+	let numerators = cells(weight).into_iter().map(|(row, column)| entries + column - row);
+	Some(cyclotomic_product(weight, q_exponents(numerators, weight)))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn hook_length_counts() {
+		assert_eq!(hook_length_count::<u8>(&[]), Some(1));
+		assert_eq!(hook_length_count::<u8>(&[3, 2]), Some(5));
+		assert_eq!(hook_length_count::<u8>(&[4, 2, 1]), Some(35));
+		assert_eq!(hook_length_count::<u8>(&[4, 4, 4, 4]), Some(24024));
+		assert_eq!(hook_length_count::<u8>(&[5, 5, 5, 5, 5]), Some(701149020));
+		assert_eq!(hook_length_count::<u8>(&[20; 20]), None);
+
+		assert_eq!(hook_length_big_count::<u8>(&[5, 5, 5, 5, 5]), BigUint::from(701149020u64));
+		// the 40th Catalan number
+		assert_eq!(
+			hook_length_big_count::<u8>(&[40, 40]).to_string(),
+			"2622127042276492108820"
+		);
+	}
+
+	#[test]
+	fn hook_content_counts() {
+		assert_eq!(hook_content_count::<u8>(&[], 3), Some(1));
+		assert_eq!(hook_content_count::<u8>(&[2, 1], 3), Some(8));
+		assert_eq!(hook_content_count::<u8>(&[2, 2], 3), Some(6));
+		assert_eq!(hook_content_count::<u8>(&[1, 1, 1], 2), Some(0));
+		assert_eq!(hook_content_count::<u8>(&[3], 4), Some(20));
+		assert_eq!(hook_content_big_count::<u8>(&[3, 3], 6), BigUint::from(490u64));
+
+		// s_(2,1)(1, q, q^2) = q + 2q^2 + 2q^3 + 2q^4 + q^5
+		let mut expected = Polynomial::zero();
+		for (exponent, coefficient) in vec![(1, 1), (2, 2), (3, 2), (4, 2), (5, 1)] {
+			expected.add_term(&[exponent], coefficient);
+		}
+		assert_eq!(q_hook_content_polynomial::<u8>(&[2, 1], 3), Some(expected));
+		assert_eq!(q_hook_content_polynomial::<u8>(&[1, 1, 1], 2), Some(Polynomial::zero()));
+	}
+
+	#[test]
+	fn q_hook_length_polynomials() {
+		use lattice_words::LatticeWords;
+
+		for weight in vec![vec![3, 2], vec![4, 2, 1], vec![3, 3, 3], vec![5, 3, 3, 1]] {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+
+			assert_eq!(
+				q_hook_length_polynomial(lattice_words.weight()),
+				Some(lattice_words.generating_function(|word| [word.major_index()]))
+			);
+		}
+
+		assert_eq!(q_hook_length_polynomial::<u8>(&[20; 20]), None);
+	}
+}
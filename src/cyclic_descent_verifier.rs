@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use cyclic_descents::CyclicDescentMap;
+use lattice_word::LatticeWord;
+use lattice_words::LatticeWords;
+use letter::Letter;
+
+const MAX_EXAMPLES: usize = 16;
+
+/// The words violating one of the cyclic descent axioms; only the first few are kept.
+#[derive(Clone, Debug, Default)]
+pub struct Counterexamples<L = u8> {
+	pub count: usize,
+	pub words: Vec<LatticeWord<Box<[L]>>>,
+}
+
+/// A cyclic descent set that occurs a different number of times than its cyclic shift.
+#[derive(Clone, Debug)]
+pub struct EquivarianceFailure<L = u8> {
+	pub cyclic_descents: Box<[usize]>,
+	pub count: usize,
+	pub shifted_count: usize,
+	pub word: LatticeWord<Box<[L]>>,
+}
+
+/// The outcome of checking the Adin–Reiner–Roichman axioms for a candidate cyclic descent map.
+#[derive(Clone, Debug, Default)]
+pub struct CyclicDescentReport<L = u8> {
+	/// Words whose cyclic descents do not restrict to their (tableau) descents on `1..n`.
+	pub restriction_failures: Counterexamples<L>,
+	/// Words whose cyclic descent set is empty or all of `1..n + 1`.
+	pub escher_failures: Counterexamples<L>,
+	/// Sets whose multiplicity is not preserved by the cyclic shift `i -> i + 1 (mod n)`.
+	pub equivariance_failures: Vec<EquivarianceFailure<L>>,
+}
+
+impl<L> Counterexamples<L>
+	where L: Letter
+{
+	#[inline]
+	fn push(&mut self, word: LatticeWord<&[L]>) {
+		self.count += 1;
+		if self.words.len() < MAX_EXAMPLES {
+			self.words.push(LatticeWord::from(&word));
+		}
+	}
+}
+
+impl<L> CyclicDescentReport<L>
+	where L: Letter
+{
+	#[inline]
+	pub fn is_valid(&self) -> bool {
+		self.restriction_failures.count == 0 && self.escher_failures.count == 0 &&
+			self.equivariance_failures.is_empty()
+	}
+}
+
+pub fn verify_cyclic_descents<F, I, L>(lattice_words: &LatticeWords<L>, mut f: F) -> CyclicDescentReport<L>
+	where F: FnMut(LatticeWord<&[L]>) -> I,
+	      I: IntoIterator<Item = usize>,
+	      L: Letter
+{
+	let n = lattice_words
+		.weight()
+		.iter()
+		.fold(0, |partial, entry| partial + entry.to_usize());
+
+	let mut report = CyclicDescentReport::default();
+	let mut distribution = HashMap::new();
+
+	let mut cyclic_descents = Vec::new();
+	let mut iter = lattice_words.streaming_iter();
+	while let Some(word) = iter.next() {
+		cyclic_descents.clear();
+		cyclic_descents.extend(f(word));
+		cyclic_descents.sort();
+		cyclic_descents.dedup();
+
+		let restriction_holds = cyclic_descents.iter().all(|descent| *descent > 0 && *descent <= n) &&
+			cyclic_descents
+				.iter()
+				.cloned()
+				.filter(|descent| *descent < n)
+				.eq(word.ascents());
+
+		if !restriction_holds {
+			report.restriction_failures.push(word);
+		}
+
+		if cyclic_descents.is_empty() || cyclic_descents.len() == n {
+			report.escher_failures.push(word);
+		}
+
+		distribution
+			.entry(cyclic_descents.clone().into_boxed_slice())
+			.or_insert_with(|| (0, LatticeWord::from(&word)))
+			.0 += 1;
+	}
+
+	for (cyclic_descents, &(count, ref word)) in &distribution {
+		let mut shifted: Vec<_> = cyclic_descents
+			.iter()
+			.map(|descent| descent % n + 1)
+			.collect();
+		shifted.sort();
+
+		let shifted_count = distribution
+			.get(&*shifted)
+			.map(|&(shifted_count, _)| shifted_count)
+			.unwrap_or(0);
+
+		if count != shifted_count {
+			report.equivariance_failures.push(EquivarianceFailure {
+				cyclic_descents: cyclic_descents.clone(),
+				count: count,
+				shifted_count: shifted_count,
+				word: word.clone(),
+			});
+		}
+	}
+
+	report
+}
+
+#[inline]
+pub fn verify_cyclic_descent_map<M, L>(lattice_words: &LatticeWords<L>, map: &M) -> CyclicDescentReport<L>
+	where M: CyclicDescentMap<L> + ?Sized,
+	      L: Letter
+{
+	verify_cyclic_descents(
+		lattice_words,
+		|word| word.cyclic_descents_with(map).collect::<Vec<_>>(),
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use cyclic_descents::CyclicDescentExtension;
+
+	#[test]
+	fn rectangle() {
+		let lattice_words = LatticeWords::new(vec![4, 4, 4]).unwrap();
+
+		let report = verify_cyclic_descents(&lattice_words, |word| {
+			word.tableau_cyclic_descents().unwrap().collect::<Vec<_>>()
+		});
+		assert!(report.is_valid());
+
+		let report = verify_cyclic_descent_map(&lattice_words, &CyclicDescentExtension::Rectangle);
+		assert!(report.is_valid());
+	}
+
+	#[test]
+	fn counterexamples() {
+		let lattice_words = LatticeWords::new(vec![3, 2]).unwrap();
+
+		let report = verify_cyclic_descents(
+			&lattice_words,
+			|word| word.ascents().collect::<Vec<_>>(),
+		);
+		assert_eq!(report.restriction_failures.count, 0);
+		assert_eq!(report.escher_failures.count, 0);
+		assert!(!report.equivariance_failures.is_empty());
+
+		let report = verify_cyclic_descents(
+			&lattice_words,
+			|word| word.descents().collect::<Vec<_>>(),
+		);
+		assert_eq!(report.restriction_failures.count, 5);
+		assert_eq!(&*report.restriction_failures.words[0], &[0, 1, 0, 1, 0]);
+
+		let report = verify_cyclic_descents(&lattice_words, |word| {
+			word.ascents().chain(1..word.len() + 1).collect::<Vec<_>>()
+		});
+		assert_eq!(report.restriction_failures.count, 5);
+		assert_eq!(report.escher_failures.count, 5);
+	}
+
+	#[test]
+	fn escher() {
+		let lattice_words = LatticeWords::new(vec![4]).unwrap();
+
+		let report = verify_cyclic_descents(
+			&lattice_words,
+			|word| word.ascents().collect::<Vec<_>>(),
+		);
+		assert_eq!(report.escher_failures.count, 1);
+		assert_eq!(&*report.escher_failures.words[0], &[0, 0, 0, 0]);
+	}
+}
@@ -0,0 +1,212 @@
+use full_deref::FullDeref;
+use lattice_word::{LatticeWord, ScentIter};
+use letter::Letter;
+
+/// Decides whether `n` belongs to the cyclic descent set of a lattice word of length `n`; the rest
+/// of the cyclic descent set is always the descent set of the corresponding tableau.
+pub trait CyclicDescentMap<L = u8>
+	where L: Letter
+{
+	fn has_cyclic_descent(&self, word: LatticeWord<&[L]>) -> bool;
+}
+
+/// The explicit cyclic descent extensions that are known for families of straight shapes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CyclicDescentExtension {
+	/// Rhoades' extension via promotion.
+	Rectangle,
+	/// Adin–Reiner–Roichman's extension for shapes `(n - k, k)` with `k >= 2`.
+	TwoRow,
+	/// The complement of the two-row extension on the conjugate tableau.
+	TwoColumn,
+	/// Adin–Reiner–Roichman's extension for a hook plus the cell `(2, 2)`.
+	NearHook,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CyclicDescentIter<T> {
+	iter: ScentIter<T>,
+	cyclic_descent: Option<usize>,
+}
+
+impl CyclicDescentExtension {
+	pub fn for_weight<L>(weight: &[L]) -> Result<Self, &'static str>
+		where L: Letter
+	{
+		let len = weight.iter().position(|row| *row == L::zero()).unwrap_or(weight.len());
+		let weight = &weight[..len];
+
+		if weight.len() < 2 || weight[1].to_usize() < 2 {
+			return Err("cyclic descent extensions do not exist for hook shapes");
+		}
+
+		if weight.iter().all(|row| *row == weight[0]) {
+			Ok(CyclicDescentExtension::Rectangle)
+		} else if weight.len() == 2 {
+			Ok(CyclicDescentExtension::TwoRow)
+		} else if weight[0].to_usize() == 2 {
+			Ok(CyclicDescentExtension::TwoColumn)
+		} else if weight[1].to_usize() == 2 && weight[2..].iter().all(|row| *row == L::one()) {
+			Ok(CyclicDescentExtension::NearHook)
+		} else {
+			Err("no cyclic descent extension is implemented for this shape")
+		}
+	}
+}
+
+// n is a cyclic descent when the last two entries of the second row are consecutive and
+// T[2][i - 1] > T[1][i] for 1 < i < k, where k is the length of the second row
+fn two_row_has_cyclic_descent<I>(word: I) -> bool
+	where I: Iterator<Item = bool> + Clone
+{
+	let k = word.clone().filter(|second_row| *second_row).count();
+
+	let mut first_row_count = 0;
+	let mut second_row_count = 0;
+	let mut previous_second_row = false;
+
+	for second_row in word {
+		if second_row {
+			second_row_count += 1;
+			if second_row_count + 1 < k && first_row_count <= second_row_count {
+				return false;
+			}
+			if second_row_count == k {
+				return previous_second_row;
+			}
+		} else {
+			first_row_count += 1;
+		}
+		previous_second_row = second_row;
+	}
+
+	false
+}
+
+impl<L> CyclicDescentMap<L> for CyclicDescentExtension
+	where L: Letter
+{
+	fn has_cyclic_descent(&self, word: LatticeWord<&[L]>) -> bool {
+		let first = match word.first() {
+			Some(first) => *first,
+			None => return false,
+		};
+
+		match *self {
+			CyclicDescentExtension::Rectangle => {
+				word
+					.tableau_cyclic_descents()
+					.map(|mut iter| iter.any(|descent| descent == word.len()))
+					.unwrap_or(false)
+			},
+			CyclicDescentExtension::TwoRow => {
+				two_row_has_cyclic_descent(word.iter().map(|row| *row != first))
+			},
+			CyclicDescentExtension::TwoColumn => {
+				let mut counts = vec![0; (*word.iter().max().unwrap() - first).to_usize() + 1];
+				let columns: Vec<_> = word
+					.iter()
+					.map(|row| {
+						let count = &mut counts[(*row - first).to_usize()];
+						*count += 1;
+						*count
+					})
+					.collect();
+
+				!two_row_has_cyclic_descent(columns.iter().map(|column| *column == 2))
+			},
+			CyclicDescentExtension::NearHook => {
+				// n is a cyclic descent when T[2][2] - 1 lies in the first column
+				let mut iter = word.iter().enumerate().filter(|&(_, row)| *row == first + L::one());
+				iter.next();
+				match iter.next() {
+					Some((index, _)) => word[index - 1] != first,
+					None => false,
+				}
+			},
+		}
+	}
+}
+
+impl<T, L> CyclicDescentIter<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	#[inline]
+	pub(crate) fn new<M>(word: LatticeWord<T>, map: &M) -> Self
+		where M: CyclicDescentMap<L> + ?Sized
+	{
+		let cyclic_descent = {
+			let word = LatticeWord::unchecked_new(&*word);
+			if map.has_cyclic_descent(word) {
+				Some(word.len())
+			} else {
+				None
+			}
+		};
+
+		CyclicDescentIter {
+			iter: word.into_ascents(),
+			cyclic_descent: cyclic_descent,
+		}
+	}
+}
+
+impl<T, L> Iterator for CyclicDescentIter<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().or_else(|| self.cyclic_descent.take())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use cyclic_descent_verifier::verify_cyclic_descent_map;
+	use lattice_words::LatticeWords;
+
+	fn assert_cyclic_descent_extension(weight: Vec<u8>, extension: CyclicDescentExtension) {
+		let lattice_words = LatticeWords::new(weight).unwrap();
+		assert_eq!(
+			CyclicDescentExtension::for_weight(lattice_words.weight()),
+			Ok(extension)
+		);
+		assert!(verify_cyclic_descent_map(&lattice_words, &extension).is_valid());
+	}
+
+	#[test]
+	fn rectangle() {
+		assert_cyclic_descent_extension(vec![3, 3, 3], CyclicDescentExtension::Rectangle);
+	}
+
+	#[test]
+	fn two_row() {
+		assert_cyclic_descent_extension(vec![4, 2], CyclicDescentExtension::TwoRow);
+		assert_cyclic_descent_extension(vec![6, 4], CyclicDescentExtension::TwoRow);
+		assert_cyclic_descent_extension(vec![7, 5], CyclicDescentExtension::TwoRow);
+	}
+
+	#[test]
+	fn two_column() {
+		assert_cyclic_descent_extension(vec![2, 2, 1, 1], CyclicDescentExtension::TwoColumn);
+		assert_cyclic_descent_extension(vec![2, 2, 2, 2, 1], CyclicDescentExtension::TwoColumn);
+	}
+
+	#[test]
+	fn near_hook() {
+		assert_cyclic_descent_extension(vec![3, 2, 1], CyclicDescentExtension::NearHook);
+		assert_cyclic_descent_extension(vec![5, 2, 1, 1, 1], CyclicDescentExtension::NearHook);
+	}
+
+	#[test]
+	fn unsupported_shapes() {
+		assert!(CyclicDescentExtension::for_weight::<u8>(&[5]).is_err());
+		assert!(CyclicDescentExtension::for_weight::<u8>(&[4, 1, 1]).is_err());
+		assert!(CyclicDescentExtension::for_weight::<u8>(&[3, 3, 1]).is_err());
+	}
+}
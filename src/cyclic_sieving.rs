@@ -0,0 +1,185 @@
+use lattice_words::LatticeWords;
+use letter::Letter;
+use polynomial::Polynomial;
+use semistandard_words::SemistandardWords;
+
+/// The two sides of the cyclic sieving phenomenon for `promotion^d`: the normalized maj generating
+/// function evaluated at `ω^d` for a primitive `n`-th root of unity `ω`, and the number of words
+/// fixed by `promotion^d`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CyclicSievingEntry {
+	pub d: usize,
+	/// `None` if the evaluation is not an integer.
+	pub evaluation: Option<i64>,
+	pub fixed_points: usize,
+}
+
+impl CyclicSievingEntry {
+	#[inline]
+	pub fn holds(&self) -> bool { self.evaluation == Some(self.fixed_points as i64) }
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+	while b != 0 {
+		let r = a % b;
+		a = b;
+		b = r;
+	}
+	a
+}
+
+// b(λ) = Σ (i - 1) λ_i
+fn offset<L>(shape: &[L]) -> usize
+	where L: Letter
+{
+	shape
+		.iter()
+		.enumerate()
+		.fold(0, |partial, (index, row)| partial + index * row.to_usize())
+}
+
+// evaluates the generating function at the powers of a primitive `n`-th root of unity and counts
+// the fixed points of the powers of an action of order `n` with the given orbit sizes
+fn entries(n: usize, generating_function: Polynomial, orbit_sizes: Vec<usize>) -> Vec<CyclicSievingEntry> {
+	(0..n)
+		.map(|d| {
+			CyclicSievingEntry {
+				d: d,
+				evaluation: generating_function
+					.evaluate_at_root_of_unity(0, n / gcd(n, d))
+					.and_then(|evaluation| evaluation.as_constant()),
+				fixed_points: orbit_sizes
+					.iter()
+					.filter(|size| d % **size == 0)
+					.fold(0, |partial, size| partial + size),
+			}
+		})
+		.collect()
+}
+
+/// Compares `q^{-b(λ)} Σ q^{maj}` at every power of a primitive `n`-th root of unity with the
+/// fixed points of the corresponding power of promotion, where `b(λ) = Σ (i - 1) λ_i` is the
+/// smallest major index.
+#[inline]
+pub fn cyclic_sieving<L>(lattice_words: &LatticeWords<L>) -> Result<Vec<CyclicSievingEntry>, &'static str>
+	where L: Letter
+{
+	cyclic_sieving_with_progress(lattice_words, |_| true)
+}
+
+/// The same as `cyclic_sieving`, calling `progress` with the number of words in the promotion
+/// orbits visited so far, every so often and once at the end. Returning `false` from `progress`
+/// stops with "interrupted".
+pub fn cyclic_sieving_with_progress<L, P>(
+	lattice_words: &LatticeWords<L>,
+	mut progress: P,
+	) -> Result<Vec<CyclicSievingEntry>, &'static str>
+	where L: Letter,
+	      P: FnMut(usize) -> bool
+{
+	let weight = lattice_words.weight();
+
+	if !weight.windows(2).all(|pair| pair[0] == pair[1]) {
+		return Err("only implemented for rectangular shapes");
+	}
+
+	let n = weight.iter().fold(0, |partial, row| partial + row.to_usize());
+	let offset = offset(weight);
+
+	// the q-hook length formula, so only the orbits need the words
+	let mut generating_function = Polynomial::zero();
+	for (exponents, coefficient) in lattice_words.maj_polynomial()?.terms() {
+		generating_function.add_term(&[exponents.get(0).cloned().unwrap_or(0) - offset], coefficient);
+	}
+
+	let mut orbit_sizes = Vec::new();
+	let mut done = 0;
+	let mut reported = 0;
+	for (_, size) in lattice_words.promotion_orbits()? {
+		orbit_sizes.push(size);
+
+		done += size;
+		if done - reported >= 1 << 16 {
+			if !progress(done) {
+				return Err("interrupted");
+			}
+			reported = done;
+		}
+	}
+	if !progress(done) {
+		return Err("interrupted");
+	}
+
+	Ok(entries(n, generating_function, orbit_sizes))
+}
+
+/// Rhoades' cyclic sieving phenomenon for semistandard tableaux of rectangular shape with entries
+/// at most `k - 1`: compares the principal specialization `q^{-b(λ)} s_λ(1, q, ..., q^(k - 1))` at
+/// every power of a primitive `k`-th root of unity with the fixed points of the corresponding power
+/// of Bender–Knuth promotion.
+pub fn semistandard_cyclic_sieving<L>(semistandard_words: &SemistandardWords<L>)
+	-> Result<Vec<CyclicSievingEntry>, &'static str>
+	where L: Letter
+{
+	let shape = semistandard_words.shape();
+
+	if !shape.windows(2).all(|pair| pair[0] == pair[1]) {
+		return Err("only implemented for rectangular shapes");
+	}
+
+	let k = semistandard_words.max_entry().to_usize() + 1;
+	let offset = offset(shape);
+
+	let generating_function = semistandard_words.generating_function(|word| [word.entry_sum() - offset]);
+
+	let orbit_sizes: Vec<_> = semistandard_words.promotion_orbits().map(|(_, size)| size).collect();
+
+	Ok(entries(k, generating_function, orbit_sizes))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn rectangles() {
+		let report = cyclic_sieving(&LatticeWords::new(vec![3, 3]).unwrap()).unwrap();
+		let fixed_points: Vec<_> = report.iter().map(|entry| entry.fixed_points).collect();
+
+		assert_eq!(fixed_points, vec![5, 0, 2, 3, 2, 0]);
+		assert!(report.iter().all(|entry| entry.holds()));
+
+		for weight in vec![vec![2, 2, 2], vec![4, 4, 4], vec![3, 3, 3, 3]] {
+			let report = cyclic_sieving(&LatticeWords::new(weight).unwrap()).unwrap();
+			assert!(report.iter().all(|entry| entry.holds()));
+		}
+
+		assert!(cyclic_sieving(&LatticeWords::new(vec![3, 2]).unwrap()).is_err());
+	}
+
+	#[test]
+	fn progress() {
+		let lattice_words = LatticeWords::new(vec![3, 3, 3]).unwrap();
+
+		let mut reports = Vec::new();
+		let report = cyclic_sieving_with_progress(&lattice_words, |done| {
+			reports.push(done);
+			true
+		});
+		assert_eq!(report, cyclic_sieving(&lattice_words));
+		assert_eq!(reports, vec![42]);
+
+		assert_eq!(cyclic_sieving_with_progress(&lattice_words, |_| false), Err("interrupted"));
+	}
+
+	#[test]
+	fn semistandard_rectangles() {
+		for (shape, max_entry) in vec![(vec![2, 2], 3), (vec![3, 3], 4), (vec![2, 2, 2], 4), (vec![4], 5)] {
+			let report = semistandard_cyclic_sieving(&SemistandardWords::new(shape, max_entry).unwrap()).unwrap();
+			assert_eq!(report.len(), max_entry as usize + 1);
+			assert!(report.iter().all(|entry| entry.holds()));
+		}
+
+		assert!(semistandard_cyclic_sieving(&SemistandardWords::new(vec![2, 1], 3).unwrap()).is_err());
+	}
+}
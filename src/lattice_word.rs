@@ -3,8 +3,11 @@ use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 use std::sync::Arc;
 
+use cyclic_descents::{CyclicDescentExtension, CyclicDescentIter, CyclicDescentMap};
 use full_deref::FullDeref;
+use letter::Letter;
 use pairs::{EnumeratedPairs, IntoPairs};
+use partition::Partition;
 
 use into_iter::IntoIter;
 
@@ -24,51 +27,67 @@ pub struct TableauCyclicDescentIter<T, U> {
 	iter: EnumeratedPairs<T>,
 	tracking_shape: U,
 	cyclic_descent: usize,
-	base: u8,
-	hole_row: u8,
-	hole_column: u8,
+	base: usize,
+	hole_row: usize,
+	hole_column: usize,
 }
 
-fn is_rectangle(word: &[u8]) -> bool {
-	if word.is_empty() {
-		return true;
-	}
+pub(crate) fn word_weight<L>(word: &[L]) -> Vec<L>
+	where L: Letter
+{
+	let first = match word.first() {
+		Some(first) => *first,
+		None => return Vec::new(),
+	};
 
-	let min = word[0];
-	let mut max = min;
+	let mut weight = vec![L::zero(); (*word.iter().max().unwrap() - first).to_usize() + 1];
+	for character in word {
+		weight[(*character - first).to_usize()] += L::one();
+	}
 
-	let mut min_count = 0;
-	let mut max_count = 0;
+	weight
+}
 
-	for character in word {
-		if *character > max {
-			max = *character;
-			max_count = 0;
-		}
+// exchanges the entries i and i + 1 if they are neither in the same row nor in the same column
+fn bender_knuth_in_place<L>(word: &mut [L], i: usize)
+	where L: Letter
+{
+	let (first, second) = (word[i - 1], word[i]);
 
-		if *character == min {
-			min_count += 1;
-		}
+	if first == second {
+		return;
+	}
 
-		if *character == max {
-			max_count += 1;
+	if second == first + L::one() {
+		let column = |row: L, end: usize| word[..end].iter().filter(|other| **other == row).count();
+		if column(first, i - 1) == column(second, i) {
+			return;
 		}
 	}
 
-	min_count == max_count
+	word.swap(i - 1, i);
 }
 
 impl<T> LatticeWord<T>
 	where T: FullDeref<Target = [u8]>
 {
-	pub fn new(inner: T) -> Result<Self, &'static str> {
+	#[inline]
+	pub fn new(inner: T) -> Result<Self, &'static str> { Self::from_letters(inner) }
+}
+
+impl<T, L> LatticeWord<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_letters(inner: T) -> Result<Self, &'static str> {
 		{
 			let inner = inner.full_deref();
 			if inner.len() > 0 {
-				let min = usize::from(*inner.iter().min().unwrap());
-				let mut counts = vec![0; usize::from(*inner.iter().max().unwrap()) + 1 - min];
+				let min = inner.iter().min().unwrap().to_usize();
+				let mut counts = vec![0; inner.iter().max().unwrap().to_usize() + 1 - min];
 				for entry in inner.iter() {
-					let entry = usize::from(*entry) - min;
+					let entry = entry.to_usize() - min;
 					counts[entry] += 1;
 					if entry > 0 && counts[entry] > counts[entry - 1] {
 						return Err("word is not a lattice word");
@@ -82,8 +101,12 @@ impl<T> LatticeWord<T>
 	#[inline]
 	pub fn unchecked_new(inner: T) -> Self { LatticeWord { inner: inner } }
 
+	/// The weight of the word, i.e. the shape of the tableau, whatever letter the word starts at.
+	#[inline]
+	pub fn shape(&self) -> Partition<L> { Partition::unchecked_new(word_weight(&*self)) }
+
 	#[inline]
-	pub fn descents(&self) -> ScentIter<&[u8]> {
+	pub fn descents(&self) -> ScentIter<&[L]> {
 		ScentIter::new(self.inner.full_deref(), Ordering::Less)
 	}
 
@@ -91,7 +114,7 @@ impl<T> LatticeWord<T>
 	pub fn into_descents(self) -> ScentIter<T> { ScentIter::new(self.inner, Ordering::Less) }
 
 	#[inline]
-	pub fn ascents(&self) -> ScentIter<&[u8]> {
+	pub fn ascents(&self) -> ScentIter<&[L]> {
 		ScentIter::new(self.inner.full_deref(), Ordering::Greater)
 	}
 
@@ -104,29 +127,38 @@ impl<T> LatticeWord<T>
 	#[inline]
 	pub fn tableau_cyclic_descents(
 		&self,
-	) -> Result<TableauCyclicDescentIter<&[u8], Box<[u8]>>, &'static str> {
+	) -> Result<TableauCyclicDescentIter<&[L], Box<[L]>>, &'static str> {
 		TableauCyclicDescentIter::new(self.inner.full_deref())
 	}
 
 	#[inline]
-	pub(crate) fn tableau_cyclic_descents_with_tracking_shape<U>(
-		&self,
-		tracking_shape: U,
-	) -> TableauCyclicDescentIter<&[u8], U>
-		where U: Deref<Target = [u8]> + DerefMut
+	pub fn into_tableau_cyclic_descents(
+		self,
+	) -> Result<TableauCyclicDescentIter<T, Box<[L]>>, &'static str> {
+		TableauCyclicDescentIter::new(self.inner)
+	}
+
+	#[inline]
+	pub fn cyclic_descents(&self) -> Result<CyclicDescentIter<&[L]>, &'static str> {
+		let extension = CyclicDescentExtension::for_weight(&*word_weight(&*self))?;
+		Ok(self.cyclic_descents_with(&extension))
+	}
+
+	#[inline]
+	pub fn cyclic_descents_with<M>(&self, map: &M) -> CyclicDescentIter<&[L]>
+		where M: CyclicDescentMap<L> + ?Sized
 	{
-		TableauCyclicDescentIter::with_tracking_shape(self.inner.full_deref(), tracking_shape)
+		CyclicDescentIter::new(LatticeWord::unchecked_new(self.inner.full_deref()), map)
 	}
 
 	#[inline]
-	pub fn into_tableau_cyclic_descents(
-		self,
-	) -> Result<TableauCyclicDescentIter<T, Box<[u8]>>, &'static str> {
-		TableauCyclicDescentIter::new(self.inner)
+	pub fn into_cyclic_descents(self) -> Result<CyclicDescentIter<T>, &'static str> {
+		let extension = CyclicDescentExtension::for_weight(&*word_weight(&*self))?;
+		Ok(CyclicDescentIter::new(self, &extension))
 	}
 
 	#[inline]
-	pub fn promotion(&self, count: Option<usize>) -> Result<LatticeWord<Box<[u8]>>, &'static str> {
+	pub fn promotion(&self, count: Option<usize>) -> LatticeWord<Box<[L]>> {
 		let mut current = 0;
 		let count = count.unwrap_or(1);
 		self.promotion_helper(|_| {
@@ -136,76 +168,94 @@ impl<T> LatticeWord<T>
 	}
 
 	#[inline]
-	pub fn promotion_order(&self) -> Result<usize, &'static str> {
+	pub fn promotion_order(&self) -> usize {
 		let mut order = 0;
 		self.promotion_helper(|word| {
 			order += 1;
 			word == &**self
-		}).map(|_| order)
+		});
+		order
 	}
 
-	fn promotion_helper<F>(&self, mut f: F) -> Result<LatticeWord<Box<[u8]>>, &'static str>
-		where F: FnMut(&[u8]) -> bool
+	fn promotion_helper<F>(&self, mut f: F) -> LatticeWord<Box<[L]>>
+		where F: FnMut(&[L]) -> bool
 	{
-		if !is_rectangle(&*self) {
-			return Err("only implemented for rectangular shapes");
+		if self.is_empty() {
+			f(&[]);
+			return LatticeWord::unchecked_new(Box::new([]));
 		}
 
-		let len = usize::from(self.last().unwrap() - self.first().unwrap()) + 1;
+		let rectangle = self.shape().is_rectangle();
+		let len = (*self.iter().max().unwrap() - *self.first().unwrap()).to_usize() + 1;
 
-		let mut tracking_shape = Vec::with_capacity(len);
-		unsafe {
-			tracking_shape.set_len(len);
-		}
+		let mut tracking_shape = vec![L::zero(); len];
 
-		let mut word = self.promotion_with_tracking_shape(&mut *tracking_shape);
+		let mut word = self.promotion_with_tracking_shape(rectangle, &mut *tracking_shape);
 
 		loop {
 			if f(&*word) {
-				return Ok(word);
+				return word;
 			}
-			word = word.promotion_with_tracking_shape(&mut *tracking_shape);
+			word = word.promotion_with_tracking_shape(rectangle, &mut *tracking_shape);
 		}
 	}
-	
-	fn promotion_with_tracking_shape<U>(&self, mut tracking_shape: U) -> LatticeWord<Box<[u8]>>
-		where U: Deref<Target = [u8]> + DerefMut
+
+	#[inline]
+	pub(crate) fn promotion_with_tracking_shape<U>(
+		&self,
+		rectangle: bool,
+		tracking_shape: U,
+	) -> LatticeWord<Box<[L]>>
+		where U: Deref<Target = [L]> + DerefMut
+	{
+		if rectangle {
+			self.rectangular_promotion_with_tracking_shape(tracking_shape)
+		} else {
+			self.general_promotion_with_tracking_shape(tracking_shape)
+		}
+	}
+
+	fn rectangular_promotion_with_tracking_shape<U>(
+		&self,
+		mut tracking_shape: U,
+	) -> LatticeWord<Box<[L]>>
+		where U: Deref<Target = [L]> + DerefMut
 	{
 		if self.is_empty() {
 			return LatticeWord::unchecked_new(Box::new([]));
 		}
 
-		let first = self.first().unwrap();
+		let first = *self.first().unwrap();
 		let (last, prefix) = self.split_last().unwrap();
 
 		let mut new_inner = {
 			let mut tmp = Vec::with_capacity(self.len());
-			tmp.push(*first);
+			tmp.push(first);
 			tmp.extend_from_slice(prefix);
 			tmp.into_boxed_slice()
 		};
 
 		let mut hole_row = *last;
-		let mut hole_column = 1;
+		let mut hole_column = L::one();
 
 		for entry in &mut *tracking_shape {
-			*entry = 0;
+			*entry = L::zero();
 		}
 
-		*tracking_shape.last_mut().unwrap() = 1;
+		*tracking_shape.last_mut().unwrap() = L::one();
 
 		for current_row in new_inner.iter_mut().rev() {
 			let current_column = tracking_shape
-				.get_mut(usize::from(*current_row - first))
+				.get_mut((*current_row - first).to_usize())
 				.unwrap();
-			*current_column += 1;
+			*current_column += L::one();
 
 			if *current_row == hole_row {
-				hole_column += 1;
+				hole_column += L::one();
 			} else if *current_column == hole_column {
 				*current_row = hole_row;
-				hole_row -= 1;
-				if hole_row == *first {
+				hole_row -= L::one();
+				if hole_row == first {
 					break;
 				}
 			}
@@ -214,16 +264,225 @@ impl<T> LatticeWord<T>
 		LatticeWord::unchecked_new(new_inner)
 	}
 
+	// columns cannot be measured from the right edge for general shapes, so the tracking shape
+	// holds the number of entries in each row that have not been visited yet
+	fn general_promotion_with_tracking_shape<U>(
+		&self,
+		mut tracking_shape: U,
+	) -> LatticeWord<Box<[L]>>
+		where U: Deref<Target = [L]> + DerefMut
+	{
+		if self.is_empty() {
+			return LatticeWord::unchecked_new(Box::new([]));
+		}
+
+		let first = *self.first().unwrap();
+		let (last, prefix) = self.split_last().unwrap();
+
+		let mut new_inner = {
+			let mut tmp = Vec::with_capacity(self.len());
+			tmp.push(first);
+			tmp.extend_from_slice(prefix);
+			tmp.into_boxed_slice()
+		};
+
+		for entry in &mut *tracking_shape {
+			*entry = L::zero();
+		}
+
+		for current_row in &*new_inner {
+			tracking_shape[(*current_row - first).to_usize()] += L::one();
+		}
+
+		// the leading entry of `new_inner` is the new smallest entry, so it is not counted
+		tracking_shape[0] -= L::one();
+
+		let mut hole_row = *last - first;
+		let mut hole_column = tracking_shape[hole_row.to_usize()];
+
+		if hole_row == L::zero() {
+			return LatticeWord::unchecked_new(new_inner);
+		}
+
+		for current_row in new_inner[1..].iter_mut().rev() {
+			let row = *current_row - first;
+			let current_column = tracking_shape.get_mut(row.to_usize()).unwrap();
+			*current_column -= L::one();
+
+			if row == hole_row && *current_column + L::one() == hole_column {
+				hole_column -= L::one();
+			} else if row + L::one() == hole_row && *current_column == hole_column {
+				*current_row = hole_row + first;
+				hole_row -= L::one();
+				if hole_row == L::zero() {
+					break;
+				}
+			}
+		}
+
+		LatticeWord::unchecked_new(new_inner)
+	}
+
+	pub fn evacuation(&self) -> LatticeWord<Box<[L]>> {
+		if self.shape().is_rectangle() {
+			return self.rectangular_evacuation();
+		}
+
+		let first = *self.first().unwrap();
+		let len = (*self.iter().max().unwrap() - first).to_usize() + 1;
+
+		let mut current: Vec<_> = self.iter().map(|row| *row - first).collect();
+		let mut new_inner = vec![L::zero(); self.len()].into_boxed_slice();
+		let mut tracking_shape = vec![L::zero(); len];
+
+		// repeatedly remove the smallest entry and slide the hole out to an outer corner, which
+		// then receives the largest remaining label
+		for start in 0..current.len() {
+			for entry in &mut tracking_shape {
+				*entry = L::zero();
+			}
+
+			tracking_shape[0] = L::one();
+
+			let mut hole_row = L::zero();
+			let mut hole_column = L::zero();
+
+			for current_row in current[start + 1..].iter_mut() {
+				let row = *current_row;
+				let current_column = tracking_shape.get_mut(row.to_usize()).unwrap();
+
+				if row == hole_row && *current_column == hole_column + L::one() {
+					hole_column += L::one();
+				} else if row == hole_row + L::one() && *current_column == hole_column {
+					*current_row = hole_row;
+					hole_row += L::one();
+				}
+
+				*current_column += L::one();
+			}
+
+			new_inner[current.len() - 1 - start] = hole_row + first;
+		}
+
+		LatticeWord::unchecked_new(new_inner)
+	}
+
+	pub fn dual_evacuation(&self) -> LatticeWord<Box<[L]>> {
+		if self.shape().is_rectangle() {
+			return self.rectangular_evacuation();
+		}
+
+		let first = *self.first().unwrap();
+		let len = (*self.iter().max().unwrap() - first).to_usize() + 1;
+
+		let mut current: Vec<_> = self.iter().map(|row| *row - first).collect();
+		let mut new_inner = vec![L::zero(); self.len()].into_boxed_slice();
+		let mut inner_shape = vec![L::zero(); len];
+		let mut tracking_shape = vec![L::zero(); len];
+
+		// repeatedly remove the largest entry and slide the hole in to an inner corner, which then
+		// receives the smallest unused label
+		for end in (1..current.len() + 1).rev() {
+			for entry in &mut tracking_shape {
+				*entry = L::zero();
+			}
+
+			for row in &current[..end] {
+				tracking_shape[row.to_usize()] += L::one();
+			}
+
+			let (last, prefix) = current[..end].split_last_mut().unwrap();
+
+			let mut hole_row = *last;
+			tracking_shape[hole_row.to_usize()] -= L::one();
+			let mut hole_column =
+				inner_shape[hole_row.to_usize()] + tracking_shape[hole_row.to_usize()];
+
+			for current_row in prefix.iter_mut().rev() {
+				let row = *current_row;
+				let current_column = {
+					let remaining = tracking_shape.get_mut(row.to_usize()).unwrap();
+					*remaining -= L::one();
+					inner_shape[row.to_usize()] + *remaining
+				};
+
+				if row == hole_row && current_column + L::one() == hole_column {
+					hole_column -= L::one();
+				} else if row + L::one() == hole_row && current_column == hole_column {
+					*current_row = hole_row;
+					hole_row -= L::one();
+				}
+			}
+
+			new_inner[current.len() - end] = hole_row + first;
+			inner_shape[hole_row.to_usize()] += L::one();
+		}
+
+		LatticeWord::unchecked_new(new_inner)
+	}
+
+	/// The Bender–Knuth involution `t_i`, which exchanges the entries `i` and `i + 1` unless they
+	/// lie in the same row or column.
+	pub fn bender_knuth(&self, i: usize) -> Result<LatticeWord<Box<[L]>>, &'static str> {
+		self.bender_knuth_product(Some(i))
+	}
+
+	/// The product `t_{i_1} t_{i_2} ... t_{i_k}` of Bender–Knuth involutions, applied from the right.
+	pub fn bender_knuth_product<I>(&self, indices: I) -> Result<LatticeWord<Box<[L]>>, &'static str>
+		where I: IntoIterator<Item = usize>,
+		      I::IntoIter: DoubleEndedIterator
+	{
+		let mut word = self.to_vec();
+		for i in indices.into_iter().rev() {
+			if i == 0 || i >= word.len() {
+				return Err("index of Bender–Knuth involution out of range");
+			}
+			bender_knuth_in_place(&mut *word, i);
+		}
+		Ok(LatticeWord::unchecked_new(word.into_boxed_slice()))
+	}
+
+	/// Promotion as the product `t_1 t_2 ... t_{n-1}` of Bender–Knuth involutions, which agrees with
+	/// `promotion` but takes quadratic time.
+	pub fn bender_knuth_promotion(&self) -> LatticeWord<Box<[L]>> {
+		let mut word = self.to_vec();
+		for i in (1..word.len()).rev() {
+			bender_knuth_in_place(&mut *word, i);
+		}
+		LatticeWord::unchecked_new(word.into_boxed_slice())
+	}
+
+	// evacuation and dual evacuation agree on rectangles, where both amount to rotating the
+	// tableau by 180 degrees and complementing its entries
+	fn rectangular_evacuation(&self) -> LatticeWord<Box<[L]>> {
+		if self.is_empty() {
+			return LatticeWord::unchecked_new(Box::new([]));
+		}
+
+		let first = *self.first().unwrap();
+		let last = *self.last().unwrap();
+
+		LatticeWord::unchecked_new(
+			self
+				.iter()
+				.rev()
+				.map(|row| last - (*row - first))
+				.collect::<Vec<_>>()
+				.into_boxed_slice(),
+		)
+	}
+
 	#[inline]
 	pub fn inner(&self) -> &T {
 		&self.inner
 	}
 }
 
-impl<T> IntoIterator for LatticeWord<T>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> IntoIterator for LatticeWord<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
-	type Item = u8;
+	type Item = L;
 	type IntoIter = IntoIter<T>;
 
 	#[inline]
@@ -232,11 +491,12 @@ impl<T> IntoIterator for LatticeWord<T>
 	}
 }
 
-impl<T> ScentIter<T>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> ScentIter<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	#[inline]
-	fn new(word: T, ordering: Ordering) -> Self {
+	pub(crate) fn new(word: T, ordering: Ordering) -> Self {
 		ScentIter {
 			iter: word.into_pairs().enumerate(),
 			ordering: ordering,
@@ -244,8 +504,9 @@ impl<T> ScentIter<T>
 	}
 }
 
-impl<'a, T> Iterator for ScentIter<T>
-	where T: FullDeref<Target = [u8]>
+impl<'a, T, L> Iterator for ScentIter<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	type Item = usize;
 
@@ -260,40 +521,39 @@ impl<'a, T> Iterator for ScentIter<T>
 	}
 }
 
-impl<'a, T> TableauCyclicDescentIter<T, Box<[u8]>>
-	where T: FullDeref<Target = [u8]>
+impl<'a, T, L> TableauCyclicDescentIter<T, Box<[L]>>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	#[inline]
 	fn new(word: T) -> Result<Self, &'static str> {
 		let len = {
 			let w = word.full_deref();
 
-			if !is_rectangle(w) {
+			if !Partition::unchecked_new(word_weight(w)).is_rectangle() {
 				return Err("only implemented for rectangular shapes");
 			}
 
-			usize::from(w.last().unwrap() - w.first().unwrap()) + 1
+			(*w.last().unwrap() - *w.first().unwrap()).to_usize() + 1
 		};
 
-		let mut tracking_shape = Vec::with_capacity(len);
-		unsafe {
-			tracking_shape.set_len(len);
-		}
+		let tracking_shape = vec![L::zero(); len];
 
 		Ok(Self::with_tracking_shape(word, tracking_shape.into_boxed_slice()))
 	}
 }
 
-impl<'a, T, U> TableauCyclicDescentIter<T, U>
-	where T: FullDeref<Target = [u8]>,
-	      U: Deref<Target = [u8]> + DerefMut
+impl<'a, T, U, L> TableauCyclicDescentIter<T, U>
+	where T: FullDeref<Target = [L]>,
+	      U: Deref<Target = [L]> + DerefMut,
+	      L: Letter
 {
 	fn with_tracking_shape(word: T, mut tracking_shape: U) -> Self {
-		tracking_shape[0] = 1;
+		tracking_shape[0] = L::one();
 		for entry in &mut tracking_shape[1..] {
-			*entry = 0;
+			*entry = L::zero();
 		}
-		let base = *word.full_deref().first().unwrap();
+		let base = word.full_deref().first().unwrap().to_usize();
 		TableauCyclicDescentIter {
 			iter: word.into_pairs().enumerate(),
 			tracking_shape: tracking_shape,
@@ -305,26 +565,24 @@ impl<'a, T, U> TableauCyclicDescentIter<T, U>
 	}
 }
 
-impl<'a, T, U> Iterator for TableauCyclicDescentIter<T, U>
-	where T: FullDeref<Target = [u8]>,
-	      U: Deref<Target = [u8]> + DerefMut
+impl<'a, T, U, L> Iterator for TableauCyclicDescentIter<T, U>
+	where T: FullDeref<Target = [L]>,
+	      U: Deref<Target = [L]> + DerefMut,
+	      L: Letter
 {
 	type Item = usize;
 
 	fn next(&mut self) -> Option<Self::Item> {
 		for (index, (first, second)) in &mut self.iter {
-			let current_row = second - self.base;
-			let current_column = self
-				.tracking_shape
-				.get_mut(usize::from(current_row))
-				.unwrap();
+			let current_row = second.to_usize() - self.base;
+			let current_column = self.tracking_shape.get_mut(current_row).unwrap();
 
-			*current_column += 1;
+			*current_column += L::one();
 
 			if current_row == self.hole_row {
 				self.cyclic_descent = 0;
 				self.hole_column += 1;
-			} else if *current_column == self.hole_column {
+			} else if current_column.to_usize() == self.hole_column {
 				self.cyclic_descent = index + 2;
 				self.hole_row += 1;
 			}
@@ -345,18 +603,20 @@ impl<'a, T, U> Iterator for TableauCyclicDescentIter<T, U>
 	}
 }
 
-impl<T> Deref for LatticeWord<T>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> Deref for LatticeWord<T>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
-	type Target = [u8];
+	type Target = [L];
 
 	#[inline]
 	fn deref(&self) -> &Self::Target { self.inner.full_deref() }
 }
 
 
-impl<'a, T> From<&'a LatticeWord<T>> for LatticeWord<Box<[u8]>>
-	where T: FullDeref<Target = [u8]>
+impl<'a, T, L> From<&'a LatticeWord<T>> for LatticeWord<Box<[L]>>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	#[inline]
 	fn from(x: &'a LatticeWord<T>) -> Self {
@@ -367,15 +627,17 @@ impl<'a, T> From<&'a LatticeWord<T>> for LatticeWord<Box<[u8]>>
 	}
 }
 
-impl<T> From<LatticeWord<T>> for LatticeWord<Rc<T>>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> From<LatticeWord<T>> for LatticeWord<Rc<T>>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	#[inline]
 	fn from(x: LatticeWord<T>) -> Self { LatticeWord::unchecked_new(Rc::new(x.inner)) }
 }
 
-impl<T> From<LatticeWord<T>> for LatticeWord<Arc<T>>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> From<LatticeWord<T>> for LatticeWord<Arc<T>>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	#[inline]
 	fn from(x: LatticeWord<T>) -> Self { LatticeWord::unchecked_new(Arc::new(x.inner)) }
@@ -384,6 +646,7 @@ impl<T> From<LatticeWord<T>> for LatticeWord<Arc<T>>
 #[cfg(test)]
 mod test {
 	use super::*;
+	use lattice_words::LatticeWords;
 
 	#[test]
 	fn scents() {
@@ -399,6 +662,13 @@ mod test {
 		assert_eq!(&*ascents, &[1, 4]);
 	}
 
+	#[test]
+	fn shape() {
+		assert_eq!(&*LatticeWord::new(&[0, 1, 0, 0, 1][..]).unwrap().shape(), &[3, 2]);
+		assert_eq!(&*LatticeWord::new(&[1, 1, 2, 3][..]).unwrap().shape(), &[2, 1, 1]);
+		assert!(LatticeWord::new(&[0, 0, 1, 1, 2, 2][..]).unwrap().shape().is_rectangle());
+	}
+
 	#[test]
 	fn tableau_cyclic_descents() {
 		let raw_lattice_word = [0, 0, 1, 0, 1, 2, 2, 1, 0, 2, 1, 2];
@@ -434,21 +704,151 @@ mod test {
 		let raw_lattice_word = [0, 0, 1, 0, 1, 2, 2, 1, 0, 2, 1, 2];
 		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
 
-		let first_promotion = lattice_word.promotion(None).unwrap();
+		let first_promotion = lattice_word.promotion(None);
 
 		assert_eq!(&*first_promotion, &[0, 0, 0, 1, 0, 1, 2, 2, 1, 1, 2, 2]);
 
-		let second_promotion = first_promotion.promotion(None).unwrap();
+		let second_promotion = first_promotion.promotion(None);
 
 		assert_eq!(&*second_promotion, &[0, 1, 0, 0, 1, 0, 1, 2, 2, 2, 1, 2]);
-		assert_eq!(second_promotion, lattice_word.promotion(Some(2)).unwrap());
+		assert_eq!(second_promotion, lattice_word.promotion(Some(2)));
 
 		let raw_lattice_word = [1, 1, 2, 1, 2, 3, 3, 2, 1, 3, 2, 3];
 		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
 
 		assert_eq!(
-			&*lattice_word.promotion(None).unwrap(),
+			&*lattice_word.promotion(None),
 			&[1, 1, 1, 2, 1, 2, 3, 3, 2, 2, 3, 3]
 		);
 	}
+
+	#[test]
+	fn general_promotion() {
+		let raw_lattice_word = [0, 0, 1];
+		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
+
+		assert_eq!(&*lattice_word.promotion(None), &[0, 1, 0]);
+		assert_eq!(&*lattice_word.promotion(Some(2)), &[0, 0, 1]);
+		assert_eq!(lattice_word.promotion_order(), 2);
+
+		let raw_lattice_word = [0, 1, 0, 2, 0, 1, 3];
+		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
+
+		assert_eq!(&*lattice_word.promotion(None), &[0, 1, 2, 0, 3, 0, 1]);
+
+		let raw_lattice_word = [1, 2, 1, 3, 1, 2, 4];
+		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
+
+		assert_eq!(&*lattice_word.promotion(None), &[1, 2, 3, 1, 4, 1, 2]);
+	}
+
+	#[test]
+	fn general_promotion_agrees_on_rectangles() {
+		let lattice_words = LatticeWords::new(vec![4, 4, 4]).unwrap();
+		let mut tracking_shape = [0; 3];
+
+		for lattice_word in lattice_words.iter() {
+			assert_eq!(
+				lattice_word.general_promotion_with_tracking_shape(&mut tracking_shape[..]),
+				lattice_word.rectangular_promotion_with_tracking_shape(&mut tracking_shape[..])
+			);
+		}
+	}
+
+	#[test]
+	fn general_promotion_is_a_permutation() {
+		let lattice_words = LatticeWords::new(vec![4, 2, 1]).unwrap();
+
+		let mut promoted: Vec<_> = lattice_words
+			.iter()
+			.map(|lattice_word| {
+				let word = lattice_word.promotion(None);
+				assert!(LatticeWord::new(&*word).is_ok());
+				word
+			})
+			.collect();
+		promoted.sort();
+		promoted.dedup();
+
+		assert_eq!(promoted.len(), lattice_words.iter().count());
+	}
+
+	#[test]
+	fn bender_knuth() {
+		let lattice_word = LatticeWord::new(vec![0, 1, 0, 2, 0, 1, 3]).unwrap();
+
+		// 1 and 2 share a column, 2 and 3 don't
+		assert_eq!(lattice_word.bender_knuth(1), Ok(LatticeWord::from(&lattice_word)));
+		assert_eq!(&*lattice_word.bender_knuth(2).unwrap(), &[0, 0, 1, 2, 0, 1, 3]);
+		assert!(lattice_word.bender_knuth(0).is_err());
+		assert!(lattice_word.bender_knuth(7).is_err());
+
+		// t_2 t_3 applies t_3 first, after which 2 and 3 share a column
+		assert_eq!(&*lattice_word.bender_knuth_product(vec![2, 3]).unwrap(), &[0, 1, 2, 0, 0, 1, 3]);
+		assert_eq!(&*lattice_word.bender_knuth_product(vec![3, 2]).unwrap(), &[0, 0, 1, 2, 0, 1, 3]);
+
+		for weight in vec![vec![4, 4, 4], vec![4, 2, 1], vec![3, 3, 2, 1]] {
+			let rectangle = weight.iter().all(|row| *row == weight[0]);
+			let mut tracking_shape = vec![0; weight.len()];
+
+			for lattice_word in LatticeWords::new(weight).unwrap().iter() {
+				let bender_knuth_promotion = lattice_word.bender_knuth_promotion();
+
+				assert_eq!(
+					bender_knuth_promotion,
+					lattice_word.promotion_with_tracking_shape(rectangle, &mut *tracking_shape)
+				);
+				assert_eq!(
+					Ok(bender_knuth_promotion),
+					lattice_word.bender_knuth_product(1..lattice_word.len())
+				);
+
+				for i in 1..lattice_word.len() {
+					let image = lattice_word.bender_knuth(i).unwrap();
+					assert!(LatticeWord::new(&*image).is_ok());
+					assert_eq!(image.bender_knuth(i), Ok(LatticeWord::from(&lattice_word)));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn evacuation() {
+		let raw_lattice_word = [0, 1, 0, 2, 0, 1, 3];
+		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
+
+		assert_eq!(&*lattice_word.evacuation(), &[0, 1, 2, 0, 1, 0, 3]);
+		assert_eq!(&*lattice_word.dual_evacuation(), &[0, 0, 0, 1, 1, 2, 3]);
+
+		for lattice_word in LatticeWords::new(vec![4, 2, 1]).unwrap().iter() {
+			let evacuation = lattice_word.evacuation();
+			let dual_evacuation = lattice_word.dual_evacuation();
+
+			assert_eq!(evacuation.evacuation(), lattice_word);
+			assert_eq!(dual_evacuation.dual_evacuation(), lattice_word);
+			assert_eq!(dual_evacuation.evacuation(), lattice_word.promotion(Some(7)));
+			assert_eq!(
+				evacuation.promotion(None).evacuation().promotion(None),
+				lattice_word
+			);
+		}
+	}
+
+	#[test]
+	fn rectangular_evacuation() {
+		let raw_lattice_word = [1, 1, 2, 1, 2, 3, 3, 2, 1, 3, 2, 3];
+		let lattice_word = LatticeWord::new(&raw_lattice_word[..]).unwrap();
+
+		assert_eq!(
+			&*lattice_word.evacuation(),
+			&[1, 2, 1, 3, 2, 1, 1, 2, 3, 2, 3, 3]
+		);
+
+		for lattice_word in LatticeWords::new(vec![3, 3, 3]).unwrap().iter() {
+			let evacuation = lattice_word.evacuation();
+
+			assert_eq!(evacuation, lattice_word.dual_evacuation());
+			assert_eq!(evacuation.evacuation(), lattice_word);
+		}
+	}
 }
@@ -1,80 +1,506 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::iter::FusedIterator;
+use std::ops::Range;
 //use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 
+use crossbeam;
+use num_bigint::BigUint;
+use rand::Rng;
+
+use counting::{hook_length_big_count, hook_length_count, q_hook_length_polynomial};
 use full_deref::FullDeref;
-use lattice_word::LatticeWord;
+use lattice_word::{word_weight, LatticeWord};
+use letter::Letter;
 use pairs::IntoPairs;
+use partition::Partition;
+use polynomial::Polynomial;
+use ranking::Ranker;
+use sampling::hook_walk;
+use statistics::Statistic;
+use transfer_matrix;
+
+// the longest `par_histogram` waits between calls of `progress`
+const PROGRESS_MILLISECONDS: u64 = 100;
+
+// the number of words a worker thread handles between updates of the shared counter
+const PROGRESS_BATCH: usize = 1 << 14;
 
 #[derive(Clone, Debug)]
-pub struct LatticeWords {
-	weight: Box<[u8]>,
+pub struct LatticeWords<L = u8> {
+	weight: Partition<L>,
 }
 
 #[derive(Clone, Debug)]
-pub struct LatticeWordsStreamingIter<T> {
+pub struct LatticeWordsStreamingIter<T, L = u8> {
 	weight: T,
 	first_pass: bool,
-	current: Box<[u8]>,
-	subweight: Box<[u8]>,
+	current: Box<[L]>,
+	subweight: Box<[L]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LatticeWordsIter<T, L = u8> {
+	inner: LatticeWordsStreamingIter<T, L>,
+}
+
+/// Streams the lattice words together with their statistics, updating them only where
+/// `LatticeWordsStreamingIter` rewrote the word.
+#[derive(Clone, Debug)]
+pub struct LatticeWordsStatisticsIter<T, L = u8> {
+	inner: LatticeWordsStreamingIter<T, L>,
+	// the major index and number of ascents contributed by the pairs from each index on
+	suffix_major_index: Box<[usize]>,
+	suffix_ascents: Box<[usize]>,
+	// for rectangles, what the letters from each index on do to `TableauCyclicDescentIter`
+	holes: Option<HoleCache>,
+}
+
+/// The statistics of a lattice word kept up to date by `LatticeWordsStatisticsIter`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct WordStatistics {
+	pub major_index: usize,
+	/// The number of tableau descents, i.e. of ascents of the word.
+	pub descents: usize,
+	/// The number of tableau cyclic descents, only for rectangles.
+	pub cyclic_descents: Option<usize>,
+}
+
+// The hole of `TableauCyclicDescentIter` always sits at the end of its row of the tracking shape, so
+// from a given index on, the flag of the last step only depends on the row of the hole. The outcome
+// for each index and row is kept until the streaming iterator rewrites the letters before that index.
+#[derive(Clone, Debug)]
+struct HoleCache {
+	// indexed by `index * rows + row`, each valid while its stamp is that of the index
+	outcomes: Box<[(usize, Outcome)]>,
+	stamps: Box<[usize]>,
+	stamp: usize,
+	rows: usize,
+	tracking_shape: Box<[usize]>,
+	// the steps whose outcome is being looked for
+	path: Vec<(usize, usize, HoleStep)>,
+}
+
+// what the letters from an index on do to the flag of the last step that moved the hole down
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+	Keep,
+	Set(bool),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum HoleStep {
+	Right,
+	Down,
+	Stay,
 }
 
 #[derive(Clone, Debug)]
-pub struct LatticeWordsIter<T> {
-	inner: LatticeWordsStreamingIter<T>,
+pub struct PromotionOrbitsIter<'a, L = u8>
+	where L: 'a
+{
+	inner: LatticeWordsStreamingIter<&'a [L], L>,
+	ranker: Ranker<'a, L>,
+	visited: Box<[u64]>,
+	rank: usize,
+	rectangle: bool,
+	tracking_shape: Box<[L]>,
 }
 
 impl LatticeWords {
 	#[inline]
-	pub fn new(mut weight: Vec<u8>) -> Result<Self, &'static str> {
-		for pair in weight.windows(2) {
-			if pair[1] > pair[0] {
-				return Err("weight is not a partition");
-			}
-		}
-		while weight.last().map(|x| *x == 0).unwrap_or(false) {
-			weight.pop();
+	pub fn new(weight: Vec<u8>) -> Result<Self, &'static str> { Self::from_weight(weight) }
+}
+
+impl<L> LatticeWords<L>
+	where L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_weight(weight: Vec<L>) -> Result<Self, &'static str> {
+		let weight = Partition::from_parts(weight).map_err(|_| "weight is not a partition")?;
+		// the largest letter plus one must fit, for Bender–Knuth involutions and evacuation
+		if weight.len() > L::max_usize() {
+			return Err("too many rows for the letter type");
 		}
-		Ok(LatticeWords { weight: weight.into_boxed_slice() })
+		Ok(LatticeWords { weight: weight })
 	}
 
 	#[inline]
-	pub fn weight(&self) -> &[u8] { &*self.weight }
+	pub fn weight(&self) -> &[L] { &*self.weight }
 
+	/// The weight as a partition, i.e. the shape of the tableaux.
 	#[inline]
-	pub fn streaming_iter(&self) -> LatticeWordsStreamingIter<&[u8]> {
+	pub fn shape(&self) -> &Partition<L> { &self.weight }
+
+	#[inline]
+	pub fn streaming_iter(&self) -> LatticeWordsStreamingIter<&[L], L> {
 		LatticeWordsStreamingIter::new(&*self.weight)
 	}
 
 	#[inline]
-	pub fn into_streaming_iter(self) -> LatticeWordsStreamingIter<Box<[u8]>> {
-		LatticeWordsStreamingIter::new(self.weight)
+	pub fn into_streaming_iter(self) -> LatticeWordsStreamingIter<Box<[L]>, L> {
+		LatticeWordsStreamingIter::new(self.weight.into_parts())
+	}
+
+	/// Starts the streaming iterator at the word of the given rank; a rank equal to the number of
+	/// lattice words gives an exhausted iterator.
+	pub fn streaming_iter_from(&self, rank: usize) -> Result<LatticeWordsStreamingIter<&[L], L>, &'static str> {
+		let count = hook_length_count(&*self.weight).ok_or("too many lattice words to rank")?;
+		if rank > count {
+			return Err("rank out of range");
+		}
+
+		let mut iter = self.streaming_iter();
+		if rank == count {
+			Ranker::new(&*self.weight)?.unrank(rank - 1, &mut *iter.current);
+			iter.first_pass = false;
+		} else {
+			Ranker::new(&*self.weight)?.unrank(rank, &mut *iter.current);
+		}
+
+		Ok(iter)
 	}
 
+	/// Streams the words with their major index, descents and, for rectangles, cyclic descents.
+	/// These are only updated for the prefix that the iterator rewrites, which is short on average,
+	/// instead of recomputed for the whole word.
 	#[inline]
-	pub fn iter(&self) -> LatticeWordsIter<&[u8]> {
+	pub fn statistics_streaming_iter(&self) -> LatticeWordsStatisticsIter<&[L], L> {
+		LatticeWordsStatisticsIter::new(self.streaming_iter(), self.weight.is_rectangle())
+	}
+
+	/// The same as `statistics_streaming_iter`, starting at the word of the given rank as in
+	/// `streaming_iter_from`.
+	#[inline]
+	pub fn statistics_streaming_iter_from(
+		&self,
+		rank: usize,
+		) -> Result<LatticeWordsStatisticsIter<&[L], L>, &'static str> {
+		Ok(LatticeWordsStatisticsIter::new(self.streaming_iter_from(rank)?, self.weight.is_rectangle()))
+	}
+
+	#[inline]
+	pub fn iter(&self) -> LatticeWordsIter<&[L], L> {
 		LatticeWordsIter { inner: self.streaming_iter() }
 	}
+
+	/// The position of the word in iteration order.
+	pub fn rank<T>(&self, word: &LatticeWord<T>) -> Result<usize, &'static str>
+		where T: FullDeref<Target = [L]>
+	{
+		if word_weight(&**word) != &*self.weight {
+			return Err("word does not have the weight of these lattice words");
+		}
+
+		let mut ranker = Ranker::new(&*self.weight)?;
+
+		Ok(match word.first() {
+			Some(&first) if first != L::zero() => {
+				let shifted: Vec<_> = word.iter().map(|letter| *letter - first).collect();
+				ranker.rank(&*shifted)
+			},
+			_ => ranker.rank(&**word),
+		})
+	}
+
+	/// The word at the given position in iteration order.
+	pub fn unrank(&self, rank: usize) -> Result<LatticeWord<Box<[L]>>, &'static str> {
+		let mut iter = self.streaming_iter_from(rank)?;
+		iter.next().map(|word| LatticeWord::from(&word)).ok_or("rank out of range")
+	}
+
+	/// A uniformly random lattice word.
+	pub fn random_element<R>(&self, rng: &mut R) -> LatticeWord<Box<[L]>>
+		where R: Rng
+	{
+		let size = self.weight.size();
+		let mut word = vec![L::zero(); size].into_boxed_slice();
+		hook_walk(&*self.weight, rng, &mut *word);
+		LatticeWord::unchecked_new(word)
+	}
+
+	/// The number of lattice words, by the hook length formula.
+	#[inline]
+	pub fn count(&self) -> BigUint { hook_length_big_count(&*self.weight) }
+
+	/// The major index generating function, by the q-hook length formula.
+	#[inline]
+	pub fn maj_polynomial(&self) -> Result<Polynomial, &'static str> {
+		q_hook_length_polynomial(&*self.weight).ok_or("too many lattice words for the coefficients to fit")
+	}
+
+	/// The joint distribution of the major index and the number of descents, computed from the
+	/// subweights and last letters of the prefixes without visiting every word.
+	#[inline]
+	pub fn maj_des_polynomial(&self) -> Result<Polynomial, &'static str> {
+		transfer_matrix::maj_des_polynomial(&self.weight, |_| true)
+	}
+
+	/// The same as `maj_des_polynomial`, calling `progress` with the number of letters placed after
+	/// each step. Returning `false` from `progress` stops with "interrupted".
+	#[inline]
+	pub fn maj_des_polynomial_with_progress<P>(&self, progress: P) -> Result<Polynomial, &'static str>
+		where P: FnMut(usize) -> bool
+	{
+		transfer_matrix::maj_des_polynomial(&self.weight, progress)
+	}
+
+	/// The same as `maj_des_polynomial` for the number of cyclic descents of `tableau_cyclic_descents`,
+	/// so only for rectangles.
+	#[inline]
+	pub fn maj_cdes_polynomial(&self) -> Result<Polynomial, &'static str> {
+		transfer_matrix::maj_cdes_polynomial(&self.weight, |_| true)
+	}
+
+	/// The same as `maj_cdes_polynomial`, with `progress` as in `maj_des_polynomial_with_progress`.
+	#[inline]
+	pub fn maj_cdes_polynomial_with_progress<P>(&self, progress: P) -> Result<Polynomial, &'static str>
+		where P: FnMut(usize) -> bool
+	{
+		transfer_matrix::maj_cdes_polynomial(&self.weight, progress)
+	}
+
+	/// The joint distribution of the given statistics, as a polynomial with one variable for each.
+	pub fn joint_distribution(&self, statistics: &[&Statistic<L>]) -> Polynomial {
+		let mut counts = HashMap::new();
+
+		let mut iter = self.statistics_streaming_iter();
+		while let Some((word, word_statistics)) = iter.next() {
+			let values: Vec<_> = statistics
+				.iter()
+				.map(|statistic| statistic.value_with(word, &word_statistics))
+				.collect();
+			*counts.entry(values).or_insert(0) += 1;
+		}
+
+		histogram_polynomial(counts)
+	}
+
+	/// Sums the monomials with the exponents returned by `f` over all lattice words.
+	pub fn generating_function<F, E>(&self, mut f: F) -> Polynomial
+		where F: FnMut(LatticeWord<&[L]>) -> E,
+		      E: AsRef<[usize]> + Eq + Hash
+	{
+		let mut counts = HashMap::new();
+
+		let mut iter = self.streaming_iter();
+		while let Some(word) = iter.next() {
+			*counts.entry(f(word)).or_insert(0) += 1;
+		}
+
+		histogram_polynomial(counts)
+	}
+
+	/// Computes `generating_function` by splitting the lattice words into rank ranges that are
+	/// processed on separate threads; `make_f` is called once per thread.
+	#[inline]
+	pub fn par_generating_function<M, F, E>(&self, threads: usize, make_f: M) -> Result<Polynomial, &'static str>
+		where M: Fn() -> F + Sync,
+		      F: FnMut(LatticeWord<&[L]>) -> E,
+		      E: AsRef<[usize]> + Eq + Hash
+	{
+		self.par_generating_function_with_progress(threads, make_f, |_| true)
+	}
+
+	/// The same as `par_generating_function`, calling `progress` on this thread with the number of
+	/// words done so far, at least every tenth of a second and once at the end. Returning `false`
+	/// from `progress` stops the worker threads and fails with "interrupted".
+	pub fn par_generating_function_with_progress<M, F, E, P>(
+		&self,
+		threads: usize,
+		make_f: M,
+		progress: P,
+		) -> Result<Polynomial, &'static str>
+		where M: Fn() -> F + Sync,
+		      F: FnMut(LatticeWord<&[L]>) -> E,
+		      E: AsRef<[usize]> + Eq + Hash,
+		      P: FnMut(usize) -> bool
+	{
+		self.par_histogram(threads, progress, |ranks, done, stop| {
+			let mut f = make_f();
+			let mut counts = HashMap::new();
+
+			let mut iter = self.streaming_iter_from(ranks.start)?;
+			in_batches(ranks, done, stop, || {
+				*counts.entry(f(iter.next().unwrap())).or_insert(0) += 1;
+			})?;
+
+			Ok(histogram_polynomial(counts))
+		})
+	}
+
+	/// The same as `par_generating_function_with_progress`, also passing `f` the statistics kept up
+	/// to date by `statistics_streaming_iter`.
+	pub fn par_statistics_generating_function_with_progress<M, F, E, P>(
+		&self,
+		threads: usize,
+		make_f: M,
+		progress: P,
+		) -> Result<Polynomial, &'static str>
+		where M: Fn() -> F + Sync,
+		      F: FnMut(LatticeWord<&[L]>, WordStatistics) -> E,
+		      E: AsRef<[usize]> + Eq + Hash,
+		      P: FnMut(usize) -> bool
+	{
+		self.par_histogram(threads, progress, |ranks, done, stop| {
+			let mut f = make_f();
+			let mut counts = HashMap::new();
+
+			let mut iter = self.statistics_streaming_iter_from(ranks.start)?;
+			in_batches(ranks, done, stop, || {
+				let (word, statistics) = iter.next().unwrap();
+				*counts.entry(f(word, statistics)).or_insert(0) += 1;
+			})?;
+
+			Ok(histogram_polynomial(counts))
+		})
+	}
+
+	// splits the ranks between the threads, which run `histogram` on their range, and adds up the
+	// results while reporting progress as in `par_generating_function_with_progress`
+	fn par_histogram<H, P>(
+		&self,
+		threads: usize,
+		mut progress: P,
+		histogram: H,
+		) -> Result<Polynomial, &'static str>
+		where H: Fn(Range<usize>, &AtomicUsize, &AtomicBool) -> Result<Polynomial, &'static str> + Sync,
+		      P: FnMut(usize) -> bool
+	{
+		let count = hook_length_count(&*self.weight).ok_or("too many lattice words to split")?;
+		let threads = cmp::max(1, cmp::min(threads, count));
+		let chunk = (count + threads - 1) / threads;
+		let histogram = &histogram;
+		let done = &AtomicUsize::new(0);
+		let stop = &AtomicBool::new(false);
+
+		let mut polynomial = Polynomial::zero();
+		let mut error = None;
+		crossbeam::scope(|scope| {
+			let (sender, receiver) = mpsc::channel();
+			for thread in 0..threads {
+				let start = cmp::min(thread * chunk, count);
+				let end = cmp::min(start + chunk, count);
+				let sender = sender.clone();
+
+				scope.spawn(move || {
+					let _ = sender.send(histogram(start..end, done, stop));
+				});
+			}
+			drop(sender);
+
+			let mut finished = 0;
+			while finished < threads {
+				match receiver.recv_timeout(Duration::from_millis(PROGRESS_MILLISECONDS)) {
+					Ok(Ok(part)) => {
+						finished += 1;
+						polynomial += &part;
+					},
+					Ok(Err(s)) => {
+						finished += 1;
+						stop.store(true, Ordering::Relaxed);
+						error = error.or(Some(s));
+					},
+					Err(RecvTimeoutError::Timeout) => {},
+					// a worker panicked, which the scope passes on
+					Err(RecvTimeoutError::Disconnected) => break,
+				}
+
+				if error.is_none() && !progress(done.load(Ordering::Relaxed)) {
+					stop.store(true, Ordering::Relaxed);
+					error = Some("interrupted");
+				}
+			}
+		});
+
+		match error {
+			Some(s) => Err(s),
+			None => Ok(polynomial),
+		}
+	}
+
+	/// Splits the lattice words into promotion orbits, yielding the first word of each orbit (in
+	/// iteration order) together with the size of the orbit.
+	pub fn promotion_orbits(&self) -> Result<PromotionOrbitsIter<L>, &'static str> {
+		let ranker = Ranker::new(&*self.weight)?;
+		let count = hook_length_count(&*self.weight).unwrap();
+
+		let rectangle = self.weight.is_rectangle();
+
+		Ok(PromotionOrbitsIter {
+			inner: self.streaming_iter(),
+			ranker: ranker,
+			visited: vec![0; (count + 63) / 64].into_boxed_slice(),
+			rank: 0,
+			rectangle: rectangle,
+			tracking_shape: vec![L::zero(); self.weight.len()].into_boxed_slice(),
+		})
+	}
 }
 
-impl IntoIterator for LatticeWords {
-	type Item = LatticeWord<Box<[u8]>>;
-	type IntoIter = LatticeWordsIter<Box<[u8]>>;
+// calls `step` once for each rank of one thread of `par_histogram`, adding to `done` after every batch
+fn in_batches<S>(
+	ranks: Range<usize>,
+	done: &AtomicUsize,
+	stop: &AtomicBool,
+	mut step: S,
+	) -> Result<(), &'static str>
+	where S: FnMut()
+{
+	let mut rank = ranks.start;
+	while rank < ranks.end {
+		let batch_end = cmp::min(rank + PROGRESS_BATCH, ranks.end);
+		for _ in rank..batch_end {
+			step();
+		}
+		done.fetch_add(batch_end - rank, Ordering::Relaxed);
+		rank = batch_end;
+
+		if stop.load(Ordering::Relaxed) {
+			return Err("interrupted");
+		}
+	}
+
+	Ok(())
+}
+
+pub(crate) fn histogram_polynomial<E>(counts: HashMap<E, i64>) -> Polynomial
+	where E: AsRef<[usize]> + Eq + Hash
+{
+	let mut polynomial = Polynomial::zero();
+	for (exponents, count) in counts {
+		polynomial.add_term(exponents.as_ref(), count);
+	}
+	polynomial
+}
+
+impl<L> IntoIterator for LatticeWords<L>
+	where L: Letter
+{
+	type Item = LatticeWord<Box<[L]>>;
+	type IntoIter = LatticeWordsIter<Box<[L]>, L>;
 
 	#[inline]
 	fn into_iter(self) -> Self::IntoIter { LatticeWordsIter { inner: self.into_streaming_iter() } }
 }
 
 #[inline]
-fn init_starting_word(word: &mut [u8], weight: &[u8]) {
-	let mut last_row = 0;
+fn init_starting_word<L>(word: &mut [L], weight: &[L])
+	where L: Letter
+{
+	let mut last_row = L::zero();
 	let mut height = weight.len();
 	let mut word_iter = word.iter_mut();
 
 	for row in weight.iter().rev() {
-		let width = *row - last_row;
+		let width = (*row - last_row).to_usize();
 		for _ in 0..width {
 			for row_index in 0..height {
-				*word_iter.next().unwrap() = row_index as u8;
+				*word_iter.next().unwrap() = L::from_usize(row_index);
 			}
 		}
 		height -= 1;
@@ -82,40 +508,48 @@ fn init_starting_word(word: &mut [u8], weight: &[u8]) {
 	}
 }
 
-impl<T> LatticeWordsStreamingIter<T>
-	where T: FullDeref<Target = [u8]>
+impl<T, L> LatticeWordsStreamingIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
 	fn new(weight: T) -> Self {
 		let size = weight
 			.full_deref()
 			.iter()
-			.fold(0, |partial, entry| partial + usize::from(*entry));
+			.fold(0, |partial, entry| partial + entry.to_usize());
+		let mut current = vec![L::zero(); size].into_boxed_slice();
+		init_starting_word(&mut *current, weight.full_deref());
+
 		LatticeWordsStreamingIter {
 			weight: weight,
 			first_pass: true,
-			current: vec![0; size].into_boxed_slice(),
-			subweight: vec![0; size].into_boxed_slice(),
+			current: current,
+			subweight: vec![L::zero(); size].into_boxed_slice(),
 		}
 	}
 
-	pub fn next(&mut self) -> Option<LatticeWord<&[u8]>> {
+	pub fn next(&mut self) -> Option<LatticeWord<&[L]>> {
+		self.advance()?;
+		Some(LatticeWord::unchecked_new(&*self.current))
+	}
+
+	// moves to the next word, returning the number of leading letters that were rewritten
+	fn advance(&mut self) -> Option<usize> {
 		if self.first_pass {
 			self.first_pass = false;
 
-			init_starting_word(&mut *self.current, self.weight.full_deref());
-
-			return Some(LatticeWord::unchecked_new(&*self.current));
+			return Some(self.current.len());
 		}
 
 		for row in &mut *self.subweight {
-			*row = 0;
+			*row = L::zero();
 		}
 
 		let first_descent = {
 			let mut iter = (&*self.current).into_pairs().enumerate();
 			loop {
 				if let Some((index, (first, second))) = iter.next() {
-					self.subweight[usize::from(first)] += 1;
+					self.subweight[first.to_usize()] += L::one();
 					if second < first {
 						break index + 1;
 					}
@@ -127,7 +561,7 @@ impl<T> LatticeWordsStreamingIter<T>
 
 		let new_row_index = {
 			let mut iter = self.subweight.iter().enumerate().rev();
-			let target = self.subweight[usize::from(self.current[first_descent]) + 1];
+			let target = self.subweight[self.current[first_descent].to_usize() + 1];
 			loop {
 				let (index, row) = iter.next().unwrap();
 				if *row == target {
@@ -137,30 +571,226 @@ impl<T> LatticeWordsStreamingIter<T>
 		};
 
 		// move the first descent into the new row
-		self.subweight[usize::from(self.current[first_descent])] += 1;
-		self.subweight[new_row_index] -= 1;
-		self.current[first_descent] = new_row_index as u8;
+		self.subweight[self.current[first_descent].to_usize()] += L::one();
+		self.subweight[new_row_index] -= L::one();
+		self.current[first_descent] = L::from_usize(new_row_index);
 
 		init_starting_word(&mut self.current[..first_descent], &*self.subweight);
 
-		Some(LatticeWord::unchecked_new(&*self.current))
+		Some(first_descent + 1)
+	}
+}
+
+impl<T, L> LatticeWordsStatisticsIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	fn new(inner: LatticeWordsStreamingIter<T, L>, rectangle: bool) -> Self {
+		let len = inner.current.len();
+		let rows = inner.weight.full_deref().len();
+
+		LatticeWordsStatisticsIter {
+			inner: inner,
+			suffix_major_index: vec![0; len].into_boxed_slice(),
+			suffix_ascents: vec![0; len].into_boxed_slice(),
+			holes: if rectangle { Some(HoleCache::new(len, rows)) } else { None },
+		}
+	}
+
+	pub fn next(&mut self) -> Option<(LatticeWord<&[L]>, WordStatistics)> {
+		let rewritten = self.inner.advance()?;
+		let word = &*self.inner.current;
+		let len = word.len();
+
+		// only the pairs that start in the rewritten prefix change
+		for index in (0..cmp::min(rewritten, len.saturating_sub(1))).rev() {
+			let (major_index, ascents) = if word[index] < word[index + 1] { (index + 1, 1) } else { (0, 0) };
+			self.suffix_major_index[index] = major_index + self.suffix_major_index[index + 1];
+			self.suffix_ascents[index] = ascents + self.suffix_ascents[index + 1];
+		}
+
+		let (major_index, descents) = if len > 0 {
+			(self.suffix_major_index[0], self.suffix_ascents[0])
+		} else {
+			(0, 0)
+		};
+
+		let cyclic_descents = match self.holes {
+			Some(ref mut holes) if len > 0 => {
+				Some(descents + if holes.cyclic_descent(word, rewritten) { 1 } else { 0 })
+			},
+			Some(_) => Some(0),
+			None => None,
+		};
+
+		Some((
+			LatticeWord::unchecked_new(word),
+			WordStatistics { major_index: major_index, descents: descents, cyclic_descents: cyclic_descents },
+		))
 	}
 }
 
-impl<T> Iterator for LatticeWordsIter<T>
-	where T: FullDeref<Target = [u8]>
+impl HoleCache {
+	fn new(len: usize, rows: usize) -> Self {
+		HoleCache {
+			// no stamp matches before the first word, which rewrites every index
+			outcomes: vec![(0, Outcome::Keep); len * rows].into_boxed_slice(),
+			stamps: vec![1; len].into_boxed_slice(),
+			stamp: 1,
+			rows: rows,
+			tracking_shape: vec![0; rows].into_boxed_slice(),
+			path: Vec::new(),
+		}
+	}
+
+	// whether the last step of `TableauCyclicDescentIter` on the word moves the hole down, where only
+	// the first `rewritten` letters changed since the previous word, keeping their weight
+	fn cyclic_descent<L>(&mut self, word: &[L], rewritten: usize) -> bool
+		where L: Letter
+	{
+		let len = word.len();
+		let rewritten = cmp::min(rewritten, len);
+
+		self.stamp += 1;
+		for stamp in &mut self.stamps[..rewritten] {
+			*stamp = self.stamp;
+		}
+
+		// replay the rewritten prefix, where the tracking shape changed
+		self.tracking_shape[word[0].to_usize()] += 1;
+		let mut row = 0;
+		let mut cyclic_descent = false;
+		for index in 1..rewritten {
+			match self.step(word, index, row) {
+				HoleStep::Right => cyclic_descent = false,
+				HoleStep::Down => {
+					row += 1;
+					cyclic_descent = true;
+				},
+				HoleStep::Stay => {},
+			}
+		}
+
+		// then go on until a known outcome, and record it for the steps on the way
+		let mut index = rewritten;
+		let mut outcome = loop {
+			if index == len {
+				break Outcome::Keep;
+			}
+			let (stamp, outcome) = self.outcomes[index * self.rows + row];
+			if stamp == self.stamps[index] {
+				break outcome;
+			}
+
+			let step = self.step(word, index, row);
+			self.path.push((index, row, step));
+			if step == HoleStep::Down {
+				row += 1;
+			}
+			index += 1;
+		};
+
+		while let Some((index, row, step)) = self.path.pop() {
+			outcome = match (step, outcome) {
+				(HoleStep::Right, Outcome::Keep) => Outcome::Set(false),
+				(HoleStep::Down, Outcome::Keep) => Outcome::Set(true),
+				_ => outcome,
+			};
+			self.outcomes[index * self.rows + row] = (self.stamps[index], outcome);
+		}
+
+		for letter in &word[..index] {
+			self.tracking_shape[letter.to_usize()] -= 1;
+		}
+
+		match outcome {
+			Outcome::Keep => cyclic_descent,
+			Outcome::Set(cyclic_descent) => cyclic_descent,
+		}
+	}
+
+	// adds the letter at the index to the tracking shape and moves the hole in the given row
+	#[inline]
+	fn step<L>(&mut self, word: &[L], index: usize, row: usize) -> HoleStep
+		where L: Letter
+	{
+		let letter = word[index].to_usize();
+		self.tracking_shape[letter] += 1;
+
+		if letter == row {
+			HoleStep::Right
+		} else if self.tracking_shape[letter] == self.tracking_shape[row] {
+			HoleStep::Down
+		} else {
+			HoleStep::Stay
+		}
+	}
+}
+
+impl<T, L> Iterator for LatticeWordsIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
 {
-	type Item = LatticeWord<Box<[u8]>>;
+	type Item = LatticeWord<Box<[L]>>;
 
 	#[inline]
 	fn next(&mut self) -> Option<Self::Item> { self.inner.next().map(|x| Self::Item::from(&x)) }
 }
 
-impl<T> FusedIterator for LatticeWordsIter<T> where Self: Iterator {}
+impl<T, L> FusedIterator for LatticeWordsIter<T, L> where Self: Iterator {}
+
+impl<'a, L> PromotionOrbitsIter<'a, L>
+	where L: Letter
+{
+	#[inline]
+	fn visit(&mut self, rank: usize) -> bool {
+		let mask = 1 << (rank % 64);
+		let block = &mut self.visited[rank / 64];
+		let visited = *block & mask != 0;
+		*block |= mask;
+		visited
+	}
+}
+
+impl<'a, L> Iterator for PromotionOrbitsIter<'a, L>
+	where L: Letter
+{
+	type Item = (LatticeWord<Box<[L]>>, usize);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			self.inner.advance()?;
+
+			let rank = self.rank;
+			self.rank += 1;
+
+			// only the first word of each orbit is copied
+			if self.visit(rank) {
+				continue;
+			}
+			let word = LatticeWord::unchecked_new(self.inner.current.clone());
+
+			let mut size = 1;
+			let mut current = word.promotion_with_tracking_shape(self.rectangle, &mut *self.tracking_shape);
+
+			while current != word {
+				let rank = self.ranker.rank(&*current);
+				self.visit(rank);
+				size += 1;
+				current = current.promotion_with_tracking_shape(self.rectangle, &mut *self.tracking_shape);
+			}
+
+			return Some((word, size));
+		}
+	}
+}
+
+impl<'a, L> FusedIterator for PromotionOrbitsIter<'a, L> where L: Letter {}
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use rand::{SeedableRng, XorShiftRng};
 
 	#[test]
 	fn basic_case() {
@@ -210,4 +840,233 @@ mod tests {
 
 		assert_eq!(n, 701149020);
 	}
+
+	#[test]
+	fn promotion_orbits() {
+		let lattice_words = LatticeWords::new(vec![3, 3]).unwrap();
+		let orbits: Vec<_> = lattice_words
+			.promotion_orbits()
+			.unwrap()
+			.map(|(word, size)| (word.to_vec(), size))
+			.collect();
+
+		assert_eq!(
+			orbits,
+			vec![(vec![0, 1, 0, 1, 0, 1], 2), (vec![0, 0, 1, 1, 0, 1], 3)]
+		);
+
+		for weight in vec![vec![4, 4, 4], vec![4, 2, 1]] {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+			let mut total = 0;
+
+			for (word, size) in lattice_words.promotion_orbits().unwrap() {
+				assert_eq!(word.promotion_order(), size);
+				total += size;
+			}
+
+			assert_eq!(total, lattice_words.iter().count());
+		}
+	}
+
+	#[test]
+	fn generating_function() {
+		// q-hook length formula for (3, 2): the hooks are 4, 3, 1, 2, 1
+		let lattice_words = LatticeWords::new(vec![3, 2]).unwrap();
+		let maj = lattice_words.generating_function(|word| [word.major_index()]);
+
+		let hooks = [4, 3, 2]
+			.iter()
+			.fold(Polynomial::constant(1), |partial, hook| &partial * &Polynomial::q_integer(0, *hook));
+		let factorial = (1..6)
+			.fold(Polynomial::monomial(&[2], 1), |partial, k| &partial * &Polynomial::q_integer(0, k));
+
+		assert_eq!(&maj * &hooks, factorial);
+
+		let maj_des = lattice_words.generating_function(|word| [word.major_index(), word.ascents().count()]);
+		assert_eq!(maj_des.coefficient(&[2, 1]), 1);
+		assert_eq!(maj_des.coefficient(&[6, 2]), 1);
+		assert_eq!(maj_des.terms().map(|(_, count)| count).sum::<i64>(), 5);
+	}
+
+	#[test]
+	fn closed_forms() {
+		let lattice_words = LatticeWords::new(vec![4, 4, 2, 1]).unwrap();
+
+		assert_eq!(lattice_words.count(), BigUint::from(lattice_words.iter().count() as u64));
+		assert_eq!(
+			lattice_words.maj_polynomial(),
+			Ok(lattice_words.generating_function(|word| [word.major_index()]))
+		);
+	}
+
+	#[test]
+	fn rank() {
+		let lattice_words = LatticeWords::new(vec![4, 2, 2, 1]).unwrap();
+		let count = lattice_words.iter().count();
+
+		for (index, word) in lattice_words.iter().enumerate() {
+			assert_eq!(lattice_words.rank(&word), Ok(index));
+			assert_eq!(lattice_words.unrank(index), Ok(word));
+		}
+		assert!(lattice_words.unrank(count).is_err());
+
+		let shifted = LatticeWord::new(vec![1, 2, 1, 2, 1]).unwrap();
+		assert_eq!(LatticeWords::new(vec![3, 2]).unwrap().rank(&shifted), Ok(0));
+		assert!(lattice_words.rank(&shifted).is_err());
+
+		let mut iter = lattice_words.streaming_iter_from(17).unwrap();
+		assert!(iter.next().unwrap().iter().eq(lattice_words.unrank(17).unwrap().iter()));
+		let mut iter = lattice_words.streaming_iter_from(count - 2).unwrap();
+		assert!(iter.next().is_some());
+		assert!(iter.next().is_some());
+		assert!(iter.next().is_none());
+		assert!(lattice_words.streaming_iter_from(count).unwrap().next().is_none());
+	}
+
+	#[test]
+	fn statistics_streaming_iter() {
+		for weight in vec![vec![], vec![3], vec![3, 2], vec![4, 3, 3, 1], vec![3, 3, 3], vec![4, 4], vec![2, 2, 2, 2]] {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+			let rectangle = lattice_words.shape().is_rectangle();
+
+			let mut count = 0;
+			let mut iter = lattice_words.statistics_streaming_iter();
+			while let Some((word, statistics)) = iter.next() {
+				assert_eq!(statistics.major_index, word.major_index());
+				assert_eq!(statistics.descents, word.ascents().count());
+				if rectangle && !word.is_empty() {
+					assert_eq!(statistics.cyclic_descents, Some(word.tableau_cyclic_descents().unwrap().count()));
+				} else if !rectangle {
+					assert_eq!(statistics.cyclic_descents, None);
+				}
+				count += 1;
+			}
+
+			assert_eq!(BigUint::from(count as u64), lattice_words.count());
+		}
+	}
+
+	#[test]
+	fn statistics_streaming_iter_from() {
+		let lattice_words = LatticeWords::new(vec![3, 3, 3]).unwrap();
+		let count = hook_length_count(lattice_words.weight()).unwrap();
+
+		for rank in vec![0, 1, 17, count - 1, count] {
+			let mut iter = lattice_words.statistics_streaming_iter_from(rank).unwrap();
+			let mut expected = lattice_words.streaming_iter_from(rank).unwrap();
+			while let Some((word, statistics)) = iter.next() {
+				assert_eq!(word, expected.next().unwrap());
+				assert_eq!(statistics.major_index, word.major_index());
+				assert_eq!(statistics.cyclic_descents, Some(word.tableau_cyclic_descents().unwrap().count()));
+			}
+			assert!(expected.next().is_none());
+		}
+
+		assert!(lattice_words.statistics_streaming_iter_from(count + 1).is_err());
+	}
+
+	#[test]
+	fn par_generating_function() {
+		let lattice_words = LatticeWords::new(vec![4, 3, 3, 1]).unwrap();
+		let maj_des = lattice_words.generating_function(|word| [word.major_index(), word.ascents().count()]);
+
+		for threads in vec![1, 3, 8] {
+			assert_eq!(
+				lattice_words.par_generating_function(
+					threads,
+					|| |word: LatticeWord<&[u8]>| [word.major_index(), word.ascents().count()],
+				),
+				Ok(maj_des.clone())
+			);
+		}
+
+		let empty = LatticeWords::new(vec![]).unwrap();
+		assert_eq!(
+			empty.par_generating_function(4, || |word: LatticeWord<&[u8]>| [word.major_index()]),
+			Ok(Polynomial::constant(1))
+		);
+	}
+
+	#[test]
+	fn par_generating_function_with_progress() {
+		let lattice_words = LatticeWords::new(vec![5, 4, 3, 2]).unwrap();
+		let count = hook_length_count(lattice_words.weight()).unwrap();
+		let maj = || |word: LatticeWord<&[u8]>| [word.major_index()];
+
+		let mut reports = Vec::new();
+		assert_eq!(
+			lattice_words.par_generating_function_with_progress(3, maj, |done| {
+				reports.push(done);
+				true
+			}),
+			Ok(lattice_words.generating_function(maj()))
+		);
+		assert!(reports.windows(2).all(|pair| pair[0] <= pair[1]));
+		assert_eq!(reports.last(), Some(&count));
+
+		let mut calls = 0;
+		assert_eq!(
+			lattice_words.par_generating_function_with_progress(3, maj, |_| {
+				calls += 1;
+				false
+			}),
+			Err("interrupted")
+		);
+		assert_eq!(calls, 1);
+	}
+
+	#[test]
+	fn par_statistics_generating_function_with_progress() {
+		let lattice_words = LatticeWords::new(vec![4, 4, 4]).unwrap();
+		let maj_cdes = lattice_words.maj_cdes_polynomial().unwrap();
+
+		for threads in vec![1, 3, 8] {
+			let mut last = 0;
+			assert_eq!(
+				lattice_words.par_statistics_generating_function_with_progress(
+					threads,
+					|| |_, statistics: WordStatistics| [statistics.major_index, statistics.cyclic_descents.unwrap()],
+					|done| {
+						last = done;
+						true
+					},
+				),
+				Ok(maj_cdes.clone())
+			);
+			assert_eq!(last, hook_length_count(lattice_words.weight()).unwrap());
+		}
+	}
+
+	#[test]
+	fn wide_letters() {
+		assert!(LatticeWords::<u8>::from_weight(vec![1; 300]).is_err());
+		assert!(LatticeWords::<u8>::from_weight(vec![2; 256]).is_err());
+
+		// the largest letter of a `u8` shape with 255 rows is 254, so one more still fits
+		let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+		let tall = LatticeWords::<u8>::from_weight(vec![2; 255]).unwrap();
+		let word = tall.random_element(&mut rng);
+		assert_eq!(word.evacuation().evacuation(), word);
+		assert_eq!(word.dual_evacuation().dual_evacuation(), word);
+		assert_eq!(word.bender_knuth_product(1..word.len()).unwrap().len(), word.len());
+		let tall = LatticeWords::<u16>::from_weight(vec![2; 256]).unwrap();
+		let word = tall.random_element(&mut rng);
+		assert_eq!(word.evacuation().evacuation(), word);
+		assert_eq!(word.dual_evacuation().dual_evacuation(), word);
+
+		let column = LatticeWords::<u16>::from_weight(vec![1; 300]).unwrap();
+		let words: Vec<_> = column.iter().collect();
+		assert_eq!(words.len(), 1);
+		assert!(words[0].iter().cloned().eq(0..300));
+		assert_eq!(words[0].promotion(None), words[0]);
+
+		let narrow = LatticeWords::new(vec![4, 2, 1]).unwrap();
+		let wide = LatticeWords::<u32>::from_weight(vec![4, 2, 1]).unwrap();
+		assert_eq!(narrow.count(), wide.count());
+		assert_eq!(narrow.maj_polynomial(), wide.maj_polynomial());
+		for (narrow_word, wide_word) in narrow.iter().zip(wide.iter()) {
+			assert!(narrow_word.iter().map(|letter| *letter as u32).eq(wide_word.iter().cloned()));
+			assert_eq!(narrow_word.major_index(), wide_word.major_index());
+		}
+	}
 }
@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// The unsigned integer types that can serve as letters of lattice words and as entries of
+/// weights. `u8` is the fast default; the wider types allow more rows or longer rows.
+pub trait Letter
+	: Copy
+	+ Debug
+	+ Default
+	+ Eq
+	+ Hash
+	+ Ord
+	+ Send
+	+ Sync
+	+ 'static
+	+ Add<Output = Self>
+	+ AddAssign
+	+ Sub<Output = Self>
+	+ SubAssign {
+	/// The largest value, as a `usize`.
+	fn max_usize() -> usize;
+
+	fn from_usize(value: usize) -> Self;
+
+	fn to_usize(self) -> usize;
+
+	#[inline]
+	fn zero() -> Self { Self::from_usize(0) }
+
+	#[inline]
+	fn one() -> Self { Self::from_usize(1) }
+}
+
+macro_rules! impl_letter {
+	($($t:ty),*) => {
+		$(
+			impl Letter for $t {
+				#[inline]
+				fn max_usize() -> usize { <$t>::max_value() as usize }
+
+				#[inline]
+				fn from_usize(value: usize) -> Self {
+					debug_assert!(value <= Self::max_usize());
+					value as $t
+				}
+
+				#[inline]
+				fn to_usize(self) -> usize { self as usize }
+			}
+		)*
+	}
+}
+
+impl_letter!(u8, u16, u32);
@@ -3,18 +3,62 @@
 
 #[macro_use]
 extern crate cpython;
+extern crate crossbeam;
+extern crate num_bigint;
+extern crate num_cpus;
+extern crate num_traits;
+extern crate rand;
 extern crate seahash;
 
+mod counting;
+mod cyclic_descent_verifier;
+mod cyclic_descents;
+mod cyclic_sieving;
 mod full_deref;
 mod into_iter;
 mod lattice_word;
 mod lattice_words;
+mod letter;
+mod littlewood_richardson;
+mod owned_slice;
 mod pairs;
+mod partition;
+mod polynomial;
 mod python;
-mod owned_slice;
+mod ranking;
+mod robinson_schensted;
+mod sampling;
+mod semistandard_word;
+mod semistandard_words;
+mod skew_lattice_word;
+mod skew_lattice_words;
+mod statistics;
+mod tableau;
+mod transfer_matrix;
 
+pub use cyclic_descent_verifier::{verify_cyclic_descent_map, verify_cyclic_descents, Counterexamples,
+                                  CyclicDescentReport, EquivarianceFailure};
+pub use cyclic_descents::{CyclicDescentExtension, CyclicDescentIter, CyclicDescentMap};
+pub use cyclic_sieving::{cyclic_sieving, cyclic_sieving_with_progress, semistandard_cyclic_sieving,
+                         CyclicSievingEntry};
 pub use lattice_word::{LatticeWord, ScentIter, TableauCyclicDescentIter};
-pub use lattice_words::{LatticeWords, LatticeWordsIter, LatticeWordsStreamingIter};
+pub use lattice_words::{LatticeWords, LatticeWordsIter, LatticeWordsStatisticsIter, LatticeWordsStreamingIter,
+                        PromotionOrbitsIter, WordStatistics};
+pub use letter::Letter;
+pub use littlewood_richardson::{littlewood_richardson_coefficient, littlewood_richardson_tableaux, schur_product,
+                               LittlewoodRichardsonTableau};
+pub use partition::Partition;
+pub use polynomial::{cyclotomic_polynomial, Polynomial};
+pub use robinson_schensted::{inverse_robinson_schensted, inverse_robinson_schensted_knuth, robinson_schensted,
+                             robinson_schensted_knuth};
+pub use semistandard_word::SemistandardWord;
+pub use semistandard_words::{SemistandardPromotionOrbitsIter, SemistandardWords, SemistandardWordsIter,
+                             SemistandardWordsStreamingIter};
+pub use skew_lattice_word::SkewLatticeWord;
+pub use skew_lattice_words::{SkewLatticeWords, SkewLatticeWordsIter, SkewLatticeWordsStreamingIter};
+pub use statistics::{statistic_by_name, CoMajorIndex, CyclicDescents, Descents, FirstLetter, Inversions, LastLetter,
+                     MajorIndex, Peaks, Statistic};
+pub use tableau::Tableau;
 
 py_module_initializer!(
 	rectangular_promotion,
@@ -23,6 +67,7 @@ py_module_initializer!(
 	|py, m| {
 		m.add(py, "LatticeWord", py.get_type::<python::LatticeWord>())?;
 		m.add(py, "LatticeWords", py.get_type::<python::LatticeWords>())?;
+		python::add_functions(py, m)?;
 		Ok(())
 	}
 );
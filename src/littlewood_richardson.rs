@@ -0,0 +1,357 @@
+use std::cmp;
+use std::collections::BTreeMap;
+
+use letter::Letter;
+use partition::is_partition;
+
+/// A Littlewood–Richardson tableau of shape `λ/μ`: a semistandard filling whose reverse reading
+/// word (rows from top to bottom, each read from right to left) is a lattice word.
+///
+/// Row `i` holds the entries of the cells in columns `μ_i..λ_i`, from left to right.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LittlewoodRichardsonTableau<L = u8> {
+	rows: Box<[Box<[L]>]>,
+}
+
+impl<L> LittlewoodRichardsonTableau<L>
+	where L: Letter
+{
+	#[inline]
+	pub fn rows(&self) -> &[Box<[L]>] { &*self.rows }
+
+	/// The reverse reading word, which is a lattice word of the weight of the tableau.
+	pub fn reading_word(&self) -> Vec<L> {
+		self.rows.iter().flat_map(|row| row.iter().rev().cloned()).collect()
+	}
+}
+
+// the cells of λ/μ in reading order, together with a backtracking search over their entries
+struct Filler<'a> {
+	outer: &'a [usize],
+	inner: &'a [usize],
+	weight: &'a [usize],
+	// the first index of each row in `entries`, which stores rows from left to right
+	offsets: Vec<usize>,
+	entries: Vec<usize>,
+	counts: Vec<usize>,
+}
+
+impl<'a> Filler<'a> {
+	fn new(outer: &'a [usize], inner: &'a [usize], weight: &'a [usize]) -> Self {
+		let mut offsets = Vec::with_capacity(outer.len());
+		let mut size = 0;
+		for (outer_row, inner_row) in outer.iter().zip(inner.iter()) {
+			offsets.push(size);
+			size += outer_row - inner_row;
+		}
+
+		Filler {
+			outer: outer,
+			inner: inner,
+			weight: weight,
+			offsets: offsets,
+			entries: vec![0; size],
+			counts: vec![0; weight.len()],
+		}
+	}
+
+	#[inline]
+	fn entry(&self, row: usize, column: usize) -> usize { self.entries[self.offsets[row] + column - self.inner[row]] }
+
+	// fills the cells from (row, column) on in reading order, where column is one past the cell
+	fn fill<F>(&mut self, row: usize, column: usize, visit: &mut F)
+		where F: FnMut(&Self)
+	{
+		if column == self.inner[row] {
+			if row + 1 == self.outer.len() {
+				visit(self);
+			} else {
+				let next_row = row + 1;
+				let next_column = self.outer[next_row];
+				self.fill(next_row, next_column, visit);
+			}
+			return;
+		}
+
+		let column = column - 1;
+
+		// rows weakly increase and columns strictly increase
+		let upper = if column + 1 < self.outer[row] {
+			self.entry(row, column + 1)
+		} else {
+			self.weight.len() - 1
+		};
+		let lower = if row > 0 && column >= self.inner[row - 1] {
+			self.entry(row - 1, column) + 1
+		} else {
+			0
+		};
+
+		for entry in lower..upper + 1 {
+			if self.counts[entry] == self.weight[entry] || (entry > 0 && self.counts[entry] == self.counts[entry - 1]) {
+				continue;
+			}
+
+			self.counts[entry] += 1;
+			let index = self.offsets[row] + column - self.inner[row];
+			self.entries[index] = entry;
+			self.fill(row, column, visit);
+			self.counts[entry] -= 1;
+		}
+	}
+}
+
+// trims trailing zeros, pads `inner` to the length of `outer` and checks the sizes; `None` if
+// there are no Littlewood–Richardson tableaux
+fn normalize<L>(outer: &[L], inner: &[L], weight: &[L])
+	-> Result<Option<(Vec<usize>, Vec<usize>, Vec<usize>)>, &'static str>
+	where L: Letter
+{
+	if !is_partition(outer) || !is_partition(inner) || !is_partition(weight) {
+		return Err("shape is not a partition");
+	}
+
+	let trimmed = |shape: &[L]| -> Vec<usize> {
+		shape.iter().map(|row| row.to_usize()).take_while(|row| *row != 0).collect()
+	};
+	let outer = trimmed(outer);
+	let mut inner = trimmed(inner);
+	let weight = trimmed(weight);
+
+	if weight.len() > L::max_usize() + 1 {
+		return Err("too many rows for the letter type");
+	}
+
+	let size = |shape: &[usize]| shape.iter().fold(0, |partial, row| partial + row);
+	if inner.len() > outer.len() ||
+		inner.iter().zip(outer.iter()).any(|(inner_row, outer_row)| inner_row > outer_row) ||
+		size(&*outer) != size(&*inner) + size(&*weight)
+	{
+		return Ok(None);
+	}
+
+	inner.resize(outer.len(), 0);
+	Ok(Some((outer, inner, weight)))
+}
+
+// calls `visit` on every Littlewood–Richardson filling of the normalized shapes
+fn for_each_filling<F>(outer: &[usize], inner: &[usize], weight: &[usize], mut visit: F)
+	where F: FnMut(&Filler)
+{
+	if outer.is_empty() {
+		visit(&Filler::new(outer, inner, weight));
+		return;
+	}
+
+	let mut filler = Filler::new(outer, inner, weight);
+	filler.fill(0, outer[0], &mut visit);
+}
+
+/// The Littlewood–Richardson tableaux of shape `outer/inner` and the given weight.
+pub fn littlewood_richardson_tableaux<L>(outer: &[L], inner: &[L], weight: &[L])
+	-> Result<Vec<LittlewoodRichardsonTableau<L>>, &'static str>
+	where L: Letter
+{
+	let (outer, inner, weight) = match normalize(outer, inner, weight)? {
+		Some(shapes) => shapes,
+		None => return Ok(Vec::new()),
+	};
+
+	let mut tableaux = Vec::new();
+	for_each_filling(&*outer, &*inner, &*weight, |filler| {
+		let rows = filler.offsets
+			.iter()
+			.zip(outer.iter().zip(inner.iter()))
+			.map(|(offset, (outer_row, inner_row))| {
+				filler.entries[*offset..*offset + outer_row - inner_row]
+					.iter()
+					.map(|entry| L::from_usize(*entry))
+					.collect::<Vec<_>>()
+					.into_boxed_slice()
+			})
+			.collect::<Vec<_>>();
+		tableaux.push(LittlewoodRichardsonTableau { rows: rows.into_boxed_slice() });
+	});
+
+	Ok(tableaux)
+}
+
+/// The Littlewood–Richardson coefficient `c^λ_{μν}`, where `λ = outer`, `μ = inner` and
+/// `ν = weight`.
+pub fn littlewood_richardson_coefficient<L>(outer: &[L], inner: &[L], weight: &[L]) -> Result<usize, &'static str>
+	where L: Letter
+{
+	let (outer, inner, weight) = match normalize(outer, inner, weight)? {
+		Some(shapes) => shapes,
+		None => return Ok(0),
+	};
+
+	let mut count = 0;
+	for_each_filling(&*outer, &*inner, &*weight, |_| count += 1);
+	Ok(count)
+}
+
+// every partition containing `inner` (padded to `rows` rows) with `cells` more cells whose rows are
+// at most `bound` longer than those of `inner`
+fn extensions(inner: &[usize], rows: usize, cells: usize, bound: usize) -> Vec<Vec<usize>> {
+	fn extend(
+		inner: &[usize],
+		bound: usize,
+		cells: usize,
+		current: &mut Vec<usize>,
+		extensions: &mut Vec<Vec<usize>>,
+	) {
+		let row = current.len();
+		if row == inner.len() {
+			if cells == 0 {
+				let len = current.iter().take_while(|entry| **entry != 0).count();
+				extensions.push(current[..len].to_vec());
+			}
+			return;
+		}
+
+		let mut largest = inner[row] + cmp::min(bound, cells);
+		if row > 0 {
+			largest = cmp::min(largest, current[row - 1]);
+		}
+
+		for length in inner[row]..largest + 1 {
+			current.push(length);
+			extend(inner, bound, cells - (length - inner[row]), current, extensions);
+			current.pop();
+		}
+	}
+
+	let mut inner = inner.to_vec();
+	inner.resize(rows, 0);
+
+	let mut extensions = Vec::new();
+	extend(&*inner, bound, cells, &mut Vec::with_capacity(rows), &mut extensions);
+	extensions
+}
+
+/// Expands the product of Schur functions `s_left * s_right` in the Schur basis, mapping each
+/// partition `λ` to `c^λ_{left, right}`.
+pub fn schur_product<L>(left: &[L], right: &[L]) -> Result<BTreeMap<Box<[L]>, usize>, &'static str>
+	where L: Letter
+{
+	if !is_partition(left) || !is_partition(right) {
+		return Err("shape is not a partition");
+	}
+
+	// use the shorter factor as the weight to get fewer entries
+	let (inner, weight) = if right.iter().filter(|row| row.to_usize() != 0).count() <=
+		left.iter().filter(|row| row.to_usize() != 0).count()
+	{
+		(left, right)
+	} else {
+		(right, left)
+	};
+
+	let as_usize = |shape: &[L]| -> Vec<usize> {
+		shape.iter().map(|row| row.to_usize()).take_while(|row| *row != 0).collect()
+	};
+	let inner = as_usize(inner);
+	let weight = as_usize(weight);
+
+	let rows = inner.len() + weight.len();
+	let cells = weight.iter().fold(0, |partial, row| partial + row);
+	// λ_i <= μ_i + ν_1 whenever c^λ_{μν} is nonzero
+	let bound = weight.first().cloned().unwrap_or(0);
+
+	if inner.first().cloned().unwrap_or(0) + bound > L::max_usize() {
+		return Err("rows are too long for the letter type");
+	}
+
+	let mut product = BTreeMap::new();
+	for outer in extensions(&*inner, rows, cells, bound) {
+		let mut padded_inner = inner.clone();
+		padded_inner.resize(outer.len(), 0);
+
+		let mut count = 0;
+		for_each_filling(&*outer, &*padded_inner, &*weight, |_| count += 1);
+
+		if count > 0 {
+			let outer: Vec<_> = outer.into_iter().map(L::from_usize).collect();
+			product.insert(outer.into_boxed_slice(), count);
+		}
+	}
+
+	Ok(product)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use lattice_word::LatticeWord;
+	use lattice_words::LatticeWords;
+	use skew_lattice_words::SkewLatticeWords;
+
+	#[test]
+	fn coefficients() {
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[3, 2, 1], &[2, 1], &[2, 1]), Ok(2));
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[4, 2], &[2, 1], &[2, 1]), Ok(1));
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[4, 2], &[3], &[2, 1]), Ok(1));
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[4, 2], &[3], &[1, 1, 1]), Ok(0));
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[4, 2], &[2, 2, 1], &[1]), Ok(0));
+		assert_eq!(littlewood_richardson_coefficient::<u8>(&[], &[], &[]), Ok(1));
+		assert!(littlewood_richardson_coefficient::<u8>(&[2, 3], &[1], &[3]).is_err());
+
+		for tableau in littlewood_richardson_tableaux::<u8>(&[4, 3, 2, 1], &[2, 1], &[3, 2, 1, 1]).unwrap() {
+			assert!(LatticeWord::new(tableau.reading_word()).is_ok());
+		}
+
+		let tableaux = littlewood_richardson_tableaux::<u8>(&[3, 2, 1], &[2, 1], &[2, 1]).unwrap();
+		let rows: Vec<_> = tableaux.iter().map(|tableau| tableau.rows().to_vec()).collect();
+		assert_eq!(
+			rows,
+			vec![
+				vec![vec![0].into_boxed_slice(), vec![0].into_boxed_slice(), vec![1].into_boxed_slice()],
+				vec![vec![0].into_boxed_slice(), vec![1].into_boxed_slice(), vec![0].into_boxed_slice()],
+			]
+		);
+	}
+
+	#[test]
+	fn skew_standard_tableaux() {
+		// f^{λ/μ} = Σ_ν c^λ_{μν} f^ν
+		let outer = vec![5, 4, 2, 1];
+		let inner = vec![3, 1];
+		let skew_count = SkewLatticeWords::new(outer.clone(), inner.clone()).unwrap().iter().count();
+
+		let mut count = 0;
+		for weight in extensions(&[], 8, 8, 8) {
+			let weight: Vec<_> = weight.into_iter().map(|row| row as u8).collect();
+			let coefficient = littlewood_richardson_coefficient(&*outer, &*inner, &*weight).unwrap();
+			count += coefficient * LatticeWords::new(weight).unwrap().iter().count();
+		}
+
+		assert_eq!(count, skew_count);
+	}
+
+	#[test]
+	fn schur_products() {
+		let product = schur_product::<u8>(&[2, 1], &[2, 1]).unwrap();
+		let expected: BTreeMap<_, _> = vec![
+			(vec![4, 2], 1),
+			(vec![4, 1, 1], 1),
+			(vec![3, 3], 1),
+			(vec![3, 2, 1], 2),
+			(vec![3, 1, 1, 1], 1),
+			(vec![2, 2, 2], 1),
+			(vec![2, 2, 1, 1], 1),
+		]
+			.into_iter()
+			.map(|(shape, coefficient)| (shape.into_boxed_slice(), coefficient))
+			.collect();
+		assert_eq!(product, expected);
+
+		// Pieri's rule
+		let product = schur_product::<u8>(&[1], &[2, 1]).unwrap();
+		let shapes: Vec<_> = product.keys().map(|shape| shape.to_vec()).collect();
+		assert_eq!(shapes, vec![vec![2, 1, 1], vec![2, 2], vec![3, 1]]);
+		assert!(product.values().all(|coefficient| *coefficient == 1));
+
+		assert_eq!(schur_product::<u8>(&[], &[]).unwrap().len(), 1);
+	}
+}
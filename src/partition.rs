@@ -0,0 +1,178 @@
+use std::ops::Deref;
+
+use letter::Letter;
+
+/// A partition, i.e. a weakly decreasing sequence of positive parts, used as the shape of
+/// tableaux. It dereferences to its parts.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Partition<L = u8> {
+	parts: Box<[L]>,
+}
+
+pub(crate) fn is_partition<L>(shape: &[L]) -> bool
+	where L: Letter
+{
+	shape.windows(2).all(|pair| pair[1] <= pair[0])
+}
+
+impl Partition {
+	#[inline]
+	pub fn new(parts: Vec<u8>) -> Result<Self, &'static str> { Self::from_parts(parts) }
+}
+
+impl<L> Partition<L>
+	where L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`. Trailing zeros are removed.
+	pub fn from_parts(mut parts: Vec<L>) -> Result<Self, &'static str> {
+		if !is_partition(&*parts) {
+			return Err("shape is not a partition");
+		}
+		while parts.last().map(|x| *x == L::zero()).unwrap_or(false) {
+			parts.pop();
+		}
+		Ok(Self::unchecked_new(parts))
+	}
+
+	#[inline]
+	pub fn unchecked_new(parts: Vec<L>) -> Self { Partition { parts: parts.into_boxed_slice() } }
+
+	#[inline]
+	pub fn into_parts(self) -> Box<[L]> { self.parts }
+
+	/// The number of cells.
+	#[inline]
+	pub fn size(&self) -> usize { self.parts.iter().fold(0, |partial, part| partial + part.to_usize()) }
+
+	/// The partition whose rows are the columns of this one.
+	pub fn conjugate(&self) -> Partition<L> {
+		let columns = self.parts.first().map(|part| part.to_usize()).unwrap_or(0);
+		let parts = (0..columns)
+			.map(|column| L::from_usize(self.parts.iter().take_while(|part| part.to_usize() > column).count()))
+			.collect();
+		Partition::unchecked_new(parts)
+	}
+
+	/// Whether the cell in the given row and column belongs to the partition.
+	#[inline]
+	pub fn has_cell(&self, row: usize, column: usize) -> bool {
+		self.parts.get(row).map(|part| part.to_usize() > column).unwrap_or(false)
+	}
+
+	/// The cells row by row, each from left to right.
+	pub fn cells(&self) -> Vec<(usize, usize)> {
+		let mut cells = Vec::with_capacity(self.size());
+		for (row, part) in self.parts.iter().enumerate() {
+			cells.extend((0..part.to_usize()).map(|column| (row, column)));
+		}
+		cells
+	}
+
+	/// The number of cells to the right of and below the given cell, plus one.
+	pub fn hook_length(&self, row: usize, column: usize) -> Option<usize> {
+		if !self.has_cell(row, column) {
+			return None;
+		}
+
+		let arm = self.parts[row].to_usize() - column - 1;
+		let leg = self.parts[row + 1..].iter().take_while(|part| part.to_usize() > column).count();
+		Some(arm + leg + 1)
+	}
+
+	/// The hook lengths of the cells, in the order of `cells`.
+	#[inline]
+	pub fn hook_lengths(&self) -> Vec<usize> {
+		self.cells().into_iter().map(|(row, column)| self.hook_length(row, column).unwrap()).collect()
+	}
+
+	/// The content `column - row` of the given cell.
+	#[inline]
+	pub fn content(&self, row: usize, column: usize) -> Option<isize> {
+		if self.has_cell(row, column) { Some(column as isize - row as isize) } else { None }
+	}
+
+	/// The contents of the cells, in the order of `cells`.
+	#[inline]
+	pub fn contents(&self) -> Vec<isize> {
+		self.cells().into_iter().map(|(row, column)| column as isize - row as isize).collect()
+	}
+
+	/// The corners, i.e. the cells whose removal leaves a partition.
+	pub fn removable_cells(&self) -> Vec<(usize, usize)> {
+		(0..self.parts.len())
+			.filter(|row| self.parts.get(row + 1).map(|next| *next < self.parts[*row]).unwrap_or(true))
+			.map(|row| (row, self.parts[row].to_usize() - 1))
+			.collect()
+	}
+
+	/// The cells whose addition gives a partition, including the one starting a new row.
+	pub fn addable_cells(&self) -> Vec<(usize, usize)> {
+		(0..self.parts.len() + 1)
+			.filter(|row| *row == 0 || self.parts.get(*row).cloned().unwrap_or(L::zero()) < self.parts[row - 1])
+			.map(|row| (row, self.parts.get(row).map(|part| part.to_usize()).unwrap_or(0)))
+			.collect()
+	}
+
+	/// Whether the Young diagram of `other` lies inside that of this partition.
+	pub fn contains(&self, other: &Partition<L>) -> bool {
+		other.len() <= self.len() && other.iter().zip(self.iter()).all(|(inner, outer)| inner <= outer)
+	}
+
+	#[inline]
+	pub fn is_rectangle(&self) -> bool { self.parts.iter().all(|part| *part == self.parts[0]) }
+}
+
+impl<L> Deref for Partition<L> {
+	type Target = [L];
+
+	#[inline]
+	fn deref(&self) -> &Self::Target { &*self.parts }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use counting::hook_length_count;
+
+	#[test]
+	fn validation() {
+		assert!(Partition::new(vec![3, 4]).is_err());
+		assert_eq!(&*Partition::new(vec![3, 1, 0, 0]).unwrap(), &[3, 1]);
+		assert_eq!(Partition::new(vec![0]), Partition::new(vec![]));
+	}
+
+	#[test]
+	fn operations() {
+		let partition = Partition::new(vec![4, 2, 2, 1]).unwrap();
+
+		assert_eq!(partition.size(), 9);
+		assert_eq!(&*partition.conjugate(), &[4, 3, 1, 1]);
+		assert_eq!(partition.conjugate().conjugate(), partition);
+		assert_eq!(partition.hook_lengths(), vec![7, 5, 2, 1, 4, 2, 3, 1, 1]);
+		assert_eq!(partition.hook_length(1, 2), None);
+		assert_eq!(partition.contents(), vec![0, 1, 2, 3, -1, 0, -2, -1, -3]);
+		assert_eq!(partition.content(3, 0), Some(-3));
+		assert_eq!(partition.removable_cells(), vec![(0, 3), (2, 1), (3, 0)]);
+		assert_eq!(partition.addable_cells(), vec![(0, 4), (1, 2), (3, 1), (4, 0)]);
+		assert_eq!(Partition::new(vec![]).unwrap().addable_cells(), vec![(0, 0)]);
+
+		assert!(partition.contains(&Partition::new(vec![4, 2, 1]).unwrap()));
+		assert!(partition.contains(&partition));
+		assert!(!partition.contains(&Partition::new(vec![3, 3]).unwrap()));
+		assert!(!partition.contains(&Partition::new(vec![1, 1, 1, 1, 1]).unwrap()));
+
+		assert!(!partition.is_rectangle());
+		assert!(Partition::new(vec![3, 3, 3]).unwrap().is_rectangle());
+		assert!(Partition::new(vec![]).unwrap().is_rectangle());
+	}
+
+	#[test]
+	fn hook_length_formula() {
+		for parts in vec![vec![4, 2, 2, 1], vec![3, 3], vec![5, 1]] {
+			let partition = Partition::new(parts).unwrap();
+			let factorial = (1..partition.size() + 1).fold(1, |partial, x| partial * x);
+			let hooks = partition.hook_lengths().into_iter().fold(1, |partial, x| partial * x);
+			assert_eq!(Some(factorial / hooks), hook_length_count(&*partition));
+		}
+	}
+}
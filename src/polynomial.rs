@@ -0,0 +1,328 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A polynomial with integer coefficients in the variables `x_0, x_1, ...`.
+///
+/// Monomials are stored as exponent vectors without trailing zeros, and zero coefficients are never
+/// stored, so that equal polynomials have equal representations.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Polynomial {
+	terms: BTreeMap<Box<[usize]>, i64>,
+}
+
+#[inline]
+fn trim(exponents: &[usize]) -> &[usize] {
+	let len = exponents.iter().rposition(|exponent| *exponent != 0).map(|index| index + 1).unwrap_or(0);
+	&exponents[..len]
+}
+
+// divides `dividend` by the monic polynomial `divisor`, both given by their coefficients in
+// increasing degree, leaving the remainder in `dividend` and returning the quotient
+fn divide(dividend: &mut Vec<i64>, divisor: &[i64]) -> Vec<i64> {
+	let degree = divisor.len() - 1;
+	let mut quotient = vec![0; dividend.len().saturating_sub(degree)];
+
+	while dividend.len() > degree {
+		let leading = dividend.pop().unwrap();
+		let offset = dividend.len() - degree;
+		quotient[offset] = leading;
+		for (coefficient, divisor_coefficient) in dividend[offset..].iter_mut().zip(divisor) {
+			*coefficient -= leading * divisor_coefficient;
+		}
+	}
+
+	quotient
+}
+
+/// The coefficients of the `order`-th cyclotomic polynomial in increasing degree.
+pub fn cyclotomic_polynomial(order: usize) -> Vec<i64> {
+	assert!(order > 0, "cyclotomic polynomials are indexed by positive integers");
+
+	let mut quotient = vec![0; order + 1];
+	quotient[0] = -1;
+	quotient[order] = 1;
+
+	for divisor in (1..order).filter(|divisor| order % divisor == 0) {
+		quotient = divide(&mut quotient, &*cyclotomic_polynomial(divisor));
+	}
+
+	quotient
+}
+
+impl Polynomial {
+	#[inline]
+	pub fn zero() -> Self { Polynomial { terms: BTreeMap::new() } }
+
+	#[inline]
+	pub fn constant(coefficient: i64) -> Self { Self::monomial(&[], coefficient) }
+
+	#[inline]
+	pub fn variable(index: usize) -> Self {
+		let mut exponents = vec![0; index + 1];
+		exponents[index] = 1;
+		Self::monomial(&*exponents, 1)
+	}
+
+	#[inline]
+	pub fn monomial(exponents: &[usize], coefficient: i64) -> Self {
+		let mut polynomial = Self::zero();
+		polynomial.add_term(exponents, coefficient);
+		polynomial
+	}
+
+	/// The polynomial `1 + x_index + ... + x_index^(n - 1)`.
+	pub fn q_integer(index: usize, n: usize) -> Self {
+		let mut exponents = vec![0; index + 1];
+		let mut polynomial = Self::zero();
+		for exponent in 0..n {
+			exponents[index] = exponent;
+			polynomial.add_term(&*exponents, 1);
+		}
+		polynomial
+	}
+
+	pub fn add_term(&mut self, exponents: &[usize], coefficient: i64) {
+		if coefficient == 0 {
+			return;
+		}
+
+		match self.terms.entry(trim(exponents).to_vec().into_boxed_slice()) {
+			Entry::Vacant(entry) => {
+				entry.insert(coefficient);
+			},
+			Entry::Occupied(mut entry) => {
+				*entry.get_mut() += coefficient;
+				if *entry.get() == 0 {
+					entry.remove();
+				}
+			},
+		}
+	}
+
+	#[inline]
+	pub fn coefficient(&self, exponents: &[usize]) -> i64 {
+		self.terms.get(trim(exponents)).cloned().unwrap_or(0)
+	}
+
+	/// The nonzero terms in increasing (lexicographic) order of their exponent vectors.
+	#[inline]
+	pub fn terms<'a>(&'a self) -> Box<Iterator<Item = (&'a [usize], i64)> + 'a> {
+		Box::new(self.terms.iter().map(|(exponents, coefficient)| (&**exponents, *coefficient)))
+	}
+
+	#[inline]
+	pub fn is_zero(&self) -> bool { self.terms.is_empty() }
+
+	/// The number of variables that actually occur.
+	#[inline]
+	pub fn variables(&self) -> usize {
+		self.terms.keys().map(|exponents| exponents.len()).max().unwrap_or(0)
+	}
+
+	#[inline]
+	pub fn as_constant(&self) -> Option<i64> {
+		match self.terms.len() {
+			0 => Some(0),
+			1 => self.terms.get(&[][..]).cloned(),
+			_ => None,
+		}
+	}
+
+	/// Substitutes a primitive `order`-th root of unity for `x_index`, provided that the result
+	/// only involves the remaining variables (with integer coefficients).
+	pub fn evaluate_at_root_of_unity(&self, index: usize, order: usize) -> Option<Self> {
+		let cyclotomic = cyclotomic_polynomial(order);
+
+		let mut groups: BTreeMap<Box<[usize]>, Vec<i64>> = BTreeMap::new();
+		for (exponents, coefficient) in &self.terms {
+			let exponent = exponents.get(index).cloned().unwrap_or(0);
+
+			let mut rest = exponents.to_vec();
+			if let Some(rest_exponent) = rest.get_mut(index) {
+				*rest_exponent = 0;
+			}
+
+			groups.entry(trim(&*rest).to_vec().into_boxed_slice()).or_insert_with(|| vec![0; order])
+				[exponent % order] += *coefficient;
+		}
+
+		let mut result = Self::zero();
+		for (rest, mut reduced) in groups {
+			divide(&mut reduced, &*cyclotomic);
+
+			if reduced.iter().skip(1).any(|coefficient| *coefficient != 0) {
+				return None;
+			}
+
+			result.add_term(&*rest, reduced.first().cloned().unwrap_or(0));
+		}
+
+		Some(result)
+	}
+
+	/// Formats the polynomial with the given variable names, e.g. as input for Sage.
+	pub fn format_with_variables<S>(&self, variables: &[S]) -> String
+		where S: AsRef<str>
+	{
+		if self.is_zero() {
+			return "0".to_owned();
+		}
+
+		let mut res = String::new();
+
+		for (exponents, coefficient) in self.terms.iter().rev() {
+			if res.is_empty() {
+				if *coefficient < 0 {
+					res.push('-');
+				}
+			} else if *coefficient < 0 {
+				res.push_str(" - ");
+			} else {
+				res.push_str(" + ");
+			}
+
+			let magnitude = coefficient.abs();
+			let mut factors = Vec::new();
+			if magnitude != 1 || exponents.is_empty() {
+				factors.push(magnitude.to_string());
+			}
+			for (index, exponent) in exponents.iter().enumerate() {
+				match *exponent {
+					0 => {},
+					1 => factors.push(variables[index].as_ref().to_owned()),
+					exponent => factors.push(format!("{}^{}", variables[index].as_ref(), exponent)),
+				}
+			}
+
+			res.push_str(&*factors.join("*"));
+		}
+
+		res
+	}
+}
+
+impl fmt::Display for Polynomial {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let variables: Vec<_> = (0..self.variables()).map(|index| format!("x{}", index)).collect();
+		f.write_str(&*self.format_with_variables(&*variables))
+	}
+}
+
+impl<'a> AddAssign<&'a Polynomial> for Polynomial {
+	fn add_assign(&mut self, other: &'a Polynomial) {
+		for (exponents, coefficient) in &other.terms {
+			self.add_term(exponents, *coefficient);
+		}
+	}
+}
+
+impl<'a, 'b> Add<&'b Polynomial> for &'a Polynomial {
+	type Output = Polynomial;
+
+	#[inline]
+	fn add(self, other: &'b Polynomial) -> Polynomial {
+		let mut sum = self.clone();
+		sum += other;
+		sum
+	}
+}
+
+impl<'a> Neg for &'a Polynomial {
+	type Output = Polynomial;
+
+	#[inline]
+	fn neg(self) -> Polynomial {
+		Polynomial {
+			terms: self.terms
+				.iter()
+				.map(|(exponents, coefficient)| (exponents.clone(), -coefficient))
+				.collect(),
+		}
+	}
+}
+
+impl<'a, 'b> Sub<&'b Polynomial> for &'a Polynomial {
+	type Output = Polynomial;
+
+	#[inline]
+	fn sub(self, other: &'b Polynomial) -> Polynomial { self + &-other }
+}
+
+impl<'a, 'b> Mul<&'b Polynomial> for &'a Polynomial {
+	type Output = Polynomial;
+
+	fn mul(self, other: &'b Polynomial) -> Polynomial {
+		let mut product = Polynomial::zero();
+		let mut exponents = Vec::new();
+
+		for (left_exponents, left_coefficient) in &self.terms {
+			for (right_exponents, right_coefficient) in &other.terms {
+				exponents.clear();
+				exponents.resize(cmp::max(left_exponents.len(), right_exponents.len()), 0);
+				for (exponent, left_exponent) in exponents.iter_mut().zip(left_exponents.iter()) {
+					*exponent += *left_exponent;
+				}
+				for (exponent, right_exponent) in exponents.iter_mut().zip(right_exponents.iter()) {
+					*exponent += *right_exponent;
+				}
+
+				product.add_term(&*exponents, left_coefficient * right_coefficient);
+			}
+		}
+
+		product
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn arithmetic() {
+		let q = Polynomial::variable(0);
+		let t = Polynomial::variable(1);
+		let one = Polynomial::constant(1);
+
+		let product = &(&q + &one) * &(&q - &one);
+		assert_eq!(product, &(&q * &q) - &one);
+		assert_eq!(product.coefficient(&[2, 0, 0]), 1);
+		assert_eq!(product.coefficient(&[1]), 0);
+
+		assert!((&product - &product).is_zero());
+		assert_eq!(Polynomial::constant(-3).as_constant(), Some(-3));
+		assert_eq!(q.as_constant(), None);
+
+		let mixed = &(&q * &t) + &Polynomial::monomial(&[0, 2], -2);
+		assert_eq!(mixed.variables(), 2);
+		assert_eq!(mixed.format_with_variables(&["q", "t"]), "q*t - 2*t^2");
+		assert_eq!((&mixed - &one).to_string(), "x0*x1 - 2*x1^2 - 1");
+	}
+
+	#[test]
+	fn roots_of_unity() {
+		assert_eq!(cyclotomic_polynomial(1), vec![-1, 1]);
+		assert_eq!(cyclotomic_polynomial(4), vec![1, 0, 1]);
+		assert_eq!(cyclotomic_polynomial(6), vec![1, -1, 1]);
+		assert_eq!(cyclotomic_polynomial(12), vec![1, 0, -1, 0, 1]);
+
+		// [3]_q vanishes at primitive cube roots of unity but is not an integer at fourth roots
+		let q_three = Polynomial::q_integer(0, 3);
+		assert_eq!(q_three.evaluate_at_root_of_unity(0, 3), Some(Polynomial::zero()));
+		assert_eq!(q_three.evaluate_at_root_of_unity(0, 4), None);
+		assert_eq!(
+			Polynomial::q_integer(0, 5).evaluate_at_root_of_unity(0, 2),
+			Some(Polynomial::constant(1))
+		);
+
+		// q^2 t + t^2 at q = -1
+		let mixed = &Polynomial::monomial(&[2, 1], 1) + &Polynomial::monomial(&[0, 2], 1);
+		assert_eq!(
+			mixed.evaluate_at_root_of_unity(0, 2),
+			Some(&Polynomial::variable(1) + &Polynomial::monomial(&[0, 2], 1))
+		);
+	}
+}
@@ -1,16 +1,27 @@
 use std::cell::RefCell;
+use std::cmp::{self, Ordering};
 use std::collections::HashMap;
 use std::fmt::Write;
-use std::{cmp, hash};
+use std::hash;
 use std::ops::Range;
 use std::sync::Arc;
 
-use cpython::{CompareOp, FromPyObject, PyClone, PyErr, PyObject, PyResult, PySlice, Python, PythonObject, ToPyObject};
-use cpython::exc::{IndexError, NotImplementedError, ValueError};
+use cpython::{CompareOp, FromPyObject, PyClone, PyDict, PyErr, PyLong, PyModule, PyObject, PyResult, PySlice, PyTuple,
+              Python, PythonObject, ToPyObject};
+// only for `PyErr_CheckSignals`, which the safe API does not wrap
+use cpython::_detail::ffi;
+use cpython::exc::{IndexError, NotImplementedError, OverflowError, ValueError};
 
+use num_cpus;
+use rand::{self, SeedableRng, StdRng};
 use seahash::SeaHasher;
 
+use cyclic_descents::CyclicDescentExtension;
+use into_iter::IntoIter;
+use letter::Letter;
 use owned_slice::OwnedSlice;
+use polynomial::Polynomial;
+use statistics::Statistic;
 
 pub struct SeaHashBuilder;
 
@@ -21,25 +32,504 @@ impl hash::BuildHasher for SeaHashBuilder {
 	fn build_hasher(&self) -> Self::Hasher { SeaHasher::new() }
 }
 
-fn generating_function<F, K>(lattice_words: &super::LatticeWords, mut f: F) -> HashMap<K, usize, SeaHashBuilder>
-	where F: FnMut(super::LatticeWord<&[u8]>) -> K,
-	      K: cmp::Eq + hash::Hash + ToPyObject,
+/// One value for each supported letter width; Python objects pick the narrowest width that fits.
+#[derive(Clone, Debug)]
+pub enum Width<A, B, C> {
+	U8(A),
+	U16(B),
+	U32(C),
+}
+
+// evaluates `$body` for whichever width `$value` has, optionally with `$wrap` bound to the
+// constructor of that width
+macro_rules! dispatch {
+	($value:expr, $inner:pat => $body:expr) => {
+		match $value {
+			Width::U8($inner) => $body,
+			Width::U16($inner) => $body,
+			Width::U32($inner) => $body,
+		}
+	};
+	($value:expr, $inner:pat, $wrap:ident => $body:expr) => {
+		match $value {
+			Width::U8($inner) => {
+				let $wrap = Width::U8;
+				$body
+			},
+			Width::U16($inner) => {
+				let $wrap = Width::U16;
+				$body
+			},
+			Width::U32($inner) => {
+				let $wrap = Width::U32;
+				$body
+			},
+		}
+	};
+}
+
+type SharedWord<L> = super::LatticeWord<Arc<Box<[L]>>>;
+type SharedSlice<L> = OwnedSlice<Arc<Box<[L]>>>;
+
+type AnyLatticeWords = Width<super::LatticeWords<u8>, super::LatticeWords<u16>, super::LatticeWords<u32>>;
+type AnyLatticeWord = Width<SharedWord<u8>, SharedWord<u16>, SharedWord<u32>>;
+type AnySlice = Width<SharedSlice<u8>, SharedSlice<u16>, SharedSlice<u32>>;
+
+#[inline]
+fn narrow<L>(letters: Vec<usize>) -> Vec<L>
+	where L: Letter
 {
-	let mut map = HashMap::with_hasher(SeaHashBuilder);
+	letters.into_iter().map(L::from_usize).collect()
+}
+
+// The width is picked so that the largest letter plus one still fits, which Bender–Knuth
+// involutions and evacuation compute.
+fn lattice_words_from_weight(weight: Vec<usize>) -> Result<AnyLatticeWords, &'static str> {
+	let largest = cmp::max(
+		weight.len().saturating_sub(1),
+		weight.iter().cloned().max().unwrap_or(0),
+	);
+
+	if largest < u8::max_usize() {
+		super::LatticeWords::from_weight(narrow(weight)).map(Width::U8)
+	} else if largest < u16::max_usize() {
+		super::LatticeWords::from_weight(narrow(weight)).map(Width::U16)
+	} else if largest < u32::max_usize() {
+		super::LatticeWords::from_weight(narrow(weight)).map(Width::U32)
+	} else {
+		Err("weight is too large")
+	}
+}
+
+fn lattice_word_from_letters(word: Vec<usize>) -> Result<AnyLatticeWord, &'static str> {
+	let largest = word.iter().cloned().max().unwrap_or(0);
+
+	if largest < u8::max_usize() {
+		super::LatticeWord::from_letters(Arc::new(narrow(word).into_boxed_slice())).map(Width::U8)
+	} else if largest < u16::max_usize() {
+		super::LatticeWord::from_letters(Arc::new(narrow(word).into_boxed_slice())).map(Width::U16)
+	} else if largest < u32::max_usize() {
+		super::LatticeWord::from_letters(Arc::new(narrow(word).into_boxed_slice())).map(Width::U32)
+	} else {
+		Err("letters are too large")
+	}
+}
+
+#[inline]
+fn shared_from_tableau<L>(tableau: &super::Tableau) -> SharedWord<L>
+	where L: Letter
+{
+	super::LatticeWord::<Box<[L]>>::from(tableau).into()
+}
+
+fn lattice_word_from_tableau(rows: Vec<Vec<usize>>) -> Result<AnyLatticeWord, &'static str> {
+	let tableau = super::Tableau::new(rows)?;
+	let largest = tableau.rows().len().saturating_sub(1);
+
+	if largest < u8::max_usize() {
+		Ok(Width::U8(shared_from_tableau(&tableau)))
+	} else if largest < u16::max_usize() {
+		Ok(Width::U16(shared_from_tableau(&tableau)))
+	} else if largest < u32::max_usize() {
+		Ok(Width::U32(shared_from_tableau(&tableau)))
+	} else {
+		Err("tableau has too many rows")
+	}
+}
+
+#[inline]
+fn letters<L>(word: &[L]) -> Vec<usize>
+	where L: Letter
+{
+	word.iter().map(|letter| letter.to_usize()).collect()
+}
+
+#[inline]
+fn compare(ordering: Ordering, op: CompareOp) -> bool {
+	match op {
+		CompareOp::Lt => ordering == Ordering::Less,
+		CompareOp::Le => ordering != Ordering::Greater,
+		CompareOp::Eq => ordering == Ordering::Equal,
+		CompareOp::Ne => ordering != Ordering::Equal,
+		CompareOp::Ge => ordering != Ordering::Less,
+		CompareOp::Gt => ordering == Ordering::Greater,
+	}
+}
+
+fn check_signals(py: Python) -> PyResult<()> {
+	if unsafe { ffi::PyErr_CheckSignals() } == 0 {
+		Ok(())
+	} else {
+		Err(PyErr::fetch(py))
+	}
+}
+
+// Runs `f` with the GIL released, so other Python threads keep going. `f` passes the number of
+// words done out of `total` to its argument, which takes the GIL back to check for signals like
+// Ctrl-C and to call `progress(done, total)`; once either raises, it returns false and the
+// exception replaces the result of `f`.
+fn without_gil<T, F>(py: Python, total: Option<usize>, progress: Option<&PyObject>, f: F) -> PyResult<T>
+	where F: FnOnce(&mut FnMut(usize) -> bool) -> Result<T, &'static str> + Send
+{
+	let mut exception = None;
+	let result = {
+		let exception = &mut exception;
+		py.allow_threads(move || {
+			f(&mut |done: usize| {
+				let gil = Python::acquire_gil();
+				let py = gil.python();
+
+				let checked = check_signals(py).and_then(|_| match progress {
+					Some(progress) => progress.call(py, (done, total), None).map(|_| ()),
+					None => Ok(()),
+				});
+				match checked {
+					Ok(()) => true,
+					Err(err) => {
+						*exception = Some(err);
+						false
+					},
+				}
+			})
+		})
+	};
+
+	match exception {
+		Some(err) => Err(err),
+		None => result.map_err(|s| PyErr::new_lazy_init(
+			py.get_type::<ValueError>(),
+			Some(s.to_py_object(py).into_object()),
+		)),
+	}
+}
+
+// the total for `progress`, from the hook length formula; `None` for Python when it does not fit
+#[inline]
+fn word_count<L>(lattice_words: &super::LatticeWords<L>) -> Option<usize>
+	where L: Letter
+{
+	::counting::hook_length_count(lattice_words.weight())
+}
+
+// The transfer matrix places one letter of every word at a time, so `placed` of `size` letters
+// count as the same share of the words, rounded down. Without a total, nothing is reported done.
+#[inline]
+fn words_done(total: Option<usize>, size: usize, placed: usize) -> usize {
+	match total {
+		Some(total) if size > 0 => total / size * placed + total % size * placed / size,
+		_ => 0,
+	}
+}
+
+fn maj_cdes<L>(
+	py: Python,
+	lattice_words: &super::LatticeWords<L>,
+	threads: Option<usize>,
+	progress: Option<&PyObject>,
+	) -> PyResult<Polynomial>
+	where L: Letter
+{
+	// rectangles need no enumeration, so `threads` only matters for the other shapes
+	if lattice_words.shape().is_rectangle() {
+		let total = word_count(lattice_words);
+		let size = lattice_words.shape().size();
+		return without_gil(py, total, progress, |report| {
+			lattice_words.maj_cdes_polynomial_with_progress(|placed| report(words_done(total, size, placed)))
+		});
+	}
+
+	let extension = CyclicDescentExtension::for_weight(lattice_words.weight()).map_err(|s| PyErr::new_lazy_init(
+		py.get_type::<NotImplementedError>(),
+		Some(s.to_py_object(py).into_object()),
+	))?;
+
+	let extension = &extension;
+	without_gil(py, word_count(lattice_words), progress, |report| {
+		lattice_words.par_statistics_generating_function_with_progress(
+			threads.unwrap_or_else(num_cpus::get),
+			|| {
+				move |word: super::LatticeWord<&[L]>, statistics: super::WordStatistics| {
+					[
+						statistics.major_index,
+						word.cyclic_descents_with(extension).count(),
+					]
+				}
+			},
+			report,
+		)
+	})
+}
+
+fn maj_des<L>(py: Python, lattice_words: &super::LatticeWords<L>, progress: Option<&PyObject>) -> PyResult<Polynomial>
+	where L: Letter
+{
+	let total = word_count(lattice_words);
+	let size = lattice_words.shape().size();
+	without_gil(py, total, progress, |report| {
+		lattice_words.maj_des_polynomial_with_progress(|placed| report(words_done(total, size, placed)))
+	})
+}
+
+fn distribution<L>(
+	py: Python,
+	lattice_words: &super::LatticeWords<L>,
+	names: &[String],
+	threads: Option<usize>,
+	progress: Option<&PyObject>,
+	) -> PyResult<PyDict>
+	where L: Letter
+{
+	let statistics = names
+		.iter()
+		.map(|name| super::statistic_by_name(&*name, lattice_words.weight()))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|s| PyErr::new_lazy_init(
+			py.get_type::<ValueError>(),
+			Some(s.to_py_object(py).into_object()),
+		))?;
+
+	let statistics = &*statistics;
+	let polynomial = without_gil(py, word_count(lattice_words), progress, |report| {
+		lattice_words.par_statistics_generating_function_with_progress(
+			threads.unwrap_or_else(num_cpus::get),
+			|| {
+				move |word: super::LatticeWord<&[L]>, word_statistics: super::WordStatistics| {
+					statistics
+						.iter()
+						.map(|statistic| statistic.value_with(word, &word_statistics))
+						.collect::<Vec<_>>()
+				}
+			},
+			report,
+		)
+	})?;
+
+	let dict = PyDict::new(py);
+	for (exponents, coefficient) in polynomial.terms() {
+		let key: Vec<_> = (0..names.len())
+			.map(|index| exponents.get(index).cloned().unwrap_or(0).to_py_object(py).into_object())
+			.collect();
+		dict.set_item(py, PyTuple::new(py, &*key), coefficient)?;
+	}
+
+	Ok(dict)
+}
+
+// Python calls need the GIL anyway, so it is held for the whole enumeration.
+fn distribution_by<L>(py: Python, lattice_words: &super::LatticeWords<L>, func: &PyObject) -> PyResult<PyDict>
+	where L: Letter
+{
+	let dict = PyDict::new(py);
+	let mut view = LatticeWordView::create_instance(py, RefCell::new(Vec::new()))?;
 
 	let mut iter = lattice_words.streaming_iter();
 	while let Some(word) = iter.next() {
-		*map.entry(f(word)).or_insert(0) += 1;
+		{
+			let mut letters = view.word(py).borrow_mut();
+			letters.clear();
+			letters.extend(word.iter().map(|letter| letter.to_usize() as u32));
+		}
+
+		let key = func.call(py, (view.clone_ref(py),), None)?;
+		let count = match dict.get_item(py, key.clone_ref(py)) {
+			Some(count) => count.extract::<u64>(py)? + 1,
+			None => 1,
+		};
+		dict.set_item(py, key, count)?;
+
+		// a view that `func` kept must not change, so the next words get a new one
+		if view.as_object().get_refcnt(py) > 1 {
+			view = LatticeWordView::create_instance(py, RefCell::new(Vec::new()))?;
+		}
+	}
+
+	Ok(dict)
+}
+
+fn orbit_sizes<L>(
+	lattice_words: &super::LatticeWords<L>,
+	report: &mut FnMut(usize) -> bool,
+	) -> Result<HashMap<usize, usize, SeaHashBuilder>, &'static str>
+	where L: Letter
+{
+	let mut map = HashMap::with_hasher(SeaHashBuilder);
+	let mut done = 0;
+	let mut reported = 0;
+	for (_, size) in lattice_words.promotion_orbits()? {
+		*map.entry(size).or_insert(0) += 1;
+
+		done += size;
+		if done - reported >= 1 << 16 {
+			if !report(done) {
+				return Err("interrupted");
+			}
+			reported = done;
+		}
+	}
+	report(done);
+	Ok(map)
+}
+
+// lattice words may use a wider letter type than a word with the same letters
+fn rank_letters<L>(lattice_words: &super::LatticeWords<L>, word: &[usize]) -> Result<usize, &'static str>
+	where L: Letter
+{
+	if word.iter().any(|letter| *letter > L::max_usize()) {
+		return Err("word does not have the weight of these lattice words");
+	}
+
+	let word: Vec<L> = narrow(word.to_vec());
+	lattice_words.rank(&super::LatticeWord::unchecked_new(&*word))
+}
+
+fn bivariate_dict(polynomial: &Polynomial) -> HashMap<(usize, usize), i64, SeaHashBuilder> {
+	let mut map = HashMap::with_hasher(SeaHashBuilder);
+
+	for (exponents, coefficient) in polynomial.terms() {
+		let exponent = |index: usize| exponents.get(index).cloned().unwrap_or(0);
+		map.insert((exponent(0), exponent(1)), coefficient);
 	}
 
 	map
 }
 
+fn shape_letters(py: Python, shape: Vec<usize>) -> PyResult<Vec<u32>> {
+	if shape.iter().any(|row| *row > u32::max_usize()) {
+		return Err(PyErr::new_lazy_init(
+			py.get_type::<ValueError>(),
+			Some("shape is too large".to_py_object(py).into_object()),
+		));
+	}
+
+	Ok(narrow(shape))
+}
+
+fn littlewood_richardson_coefficient(
+	py: Python,
+	outer: Vec<usize>,
+	inner: Vec<usize>,
+	weight: Vec<usize>,
+	) -> PyResult<usize>
+{
+	let outer = shape_letters(py, outer)?;
+	let inner = shape_letters(py, inner)?;
+	let weight = shape_letters(py, weight)?;
+
+	super::littlewood_richardson_coefficient(&*outer, &*inner, &*weight).map_err(|s| PyErr::new_lazy_init(
+		py.get_type::<ValueError>(),
+		Some(s.to_py_object(py).into_object()),
+	))
+}
+
+fn schur_product(py: Python, left: Vec<usize>, right: Vec<usize>) -> PyResult<PyDict> {
+	let left = shape_letters(py, left)?;
+	let right = shape_letters(py, right)?;
+
+	let product = super::schur_product(&*left, &*right).map_err(|s| PyErr::new_lazy_init(
+		py.get_type::<ValueError>(),
+		Some(s.to_py_object(py).into_object()),
+	))?;
+
+	let dict = PyDict::new(py);
+	for (shape, coefficient) in product {
+		let shape: Vec<_> = shape.iter().map(|row| row.to_usize().to_py_object(py).into_object()).collect();
+		dict.set_item(py, PyTuple::new(py, &*shape), coefficient)?;
+	}
+
+	Ok(dict)
+}
+
+fn robinson_schensted_pair<L>(permutation: Vec<usize>) -> Result<(SharedWord<L>, SharedWord<L>), &'static str>
+	where L: Letter
+{
+	let (insertion, recording) = super::robinson_schensted(&*narrow::<L>(permutation))?;
+	Ok((insertion.into(), recording.into()))
+}
+
+fn robinson_schensted(py: Python, permutation: Vec<usize>) -> PyResult<PyTuple> {
+	// the letters of both tableaux are smaller than the length, so checking that avoids truncation
+	let largest = permutation.len().saturating_sub(1);
+
+	let pair = if permutation.iter().any(|entry| *entry > largest) {
+		Err("word is not a permutation")
+	} else if largest < u8::max_usize() {
+		robinson_schensted_pair(permutation).map(|(insertion, recording)| (Width::U8(insertion), Width::U8(recording)))
+	} else if largest < u16::max_usize() {
+		robinson_schensted_pair(permutation).map(|(insertion, recording)| (Width::U16(insertion), Width::U16(recording)))
+	} else if largest < u32::max_usize() {
+		robinson_schensted_pair(permutation).map(|(insertion, recording)| (Width::U32(insertion), Width::U32(recording)))
+	} else {
+		Err("permutation is too large")
+	};
+
+	let (insertion, recording) = pair.map_err(|s| PyErr::new_lazy_init(
+		py.get_type::<ValueError>(),
+		Some(s.to_py_object(py).into_object()),
+	))?;
+
+	Ok(PyTuple::new(
+		py,
+		&[
+			LatticeWord::create_instance(py, insertion)?.into_object(),
+			LatticeWord::create_instance(py, recording)?.into_object(),
+		],
+	))
+}
+
+fn inverse_robinson_schensted_letters<L>(insertion: Vec<usize>, recording: Vec<usize>) -> Result<Vec<usize>, &'static str>
+	where L: Letter
+{
+	let insertion = super::LatticeWord::unchecked_new(narrow::<L>(insertion));
+	let recording = super::LatticeWord::unchecked_new(narrow::<L>(recording));
+	super::inverse_robinson_schensted(&insertion, &recording).map(|permutation| letters(&*permutation))
+}
+
+fn inverse_robinson_schensted(py: Python, insertion: LatticeWord, recording: LatticeWord) -> PyResult<Vec<usize>> {
+	// the two words may have different widths
+	let insertion = dispatch!(*insertion.lattice_word(py), ref lattice_word => letters(&**lattice_word));
+	let recording = dispatch!(*recording.lattice_word(py), ref lattice_word => letters(&**lattice_word));
+	let largest = cmp::max(
+		insertion.len().saturating_sub(1),
+		insertion.iter().chain(recording.iter()).cloned().max().unwrap_or(0),
+	);
+
+	let permutation = if largest < u8::max_usize() {
+		inverse_robinson_schensted_letters::<u8>(insertion, recording)
+	} else if largest < u16::max_usize() {
+		inverse_robinson_schensted_letters::<u16>(insertion, recording)
+	} else if largest < u32::max_usize() {
+		inverse_robinson_schensted_letters::<u32>(insertion, recording)
+	} else {
+		Err("letters are too large")
+	};
+
+	permutation.map_err(|s| PyErr::new_lazy_init(
+		py.get_type::<ValueError>(),
+		Some(s.to_py_object(py).into_object()),
+	))
+}
+
+pub fn add_functions(py: Python, m: &PyModule) -> PyResult<()> {
+	m.add(
+		py,
+		"littlewood_richardson_coefficient",
+		py_fn!(py, littlewood_richardson_coefficient(outer: Vec<usize>, inner: Vec<usize>, weight: Vec<usize>)),
+	)?;
+	m.add(py, "schur_product", py_fn!(py, schur_product(left: Vec<usize>, right: Vec<usize>)))?;
+	m.add(py, "robinson_schensted", py_fn!(py, robinson_schensted(permutation: Vec<usize>)))?;
+	m.add(
+		py,
+		"inverse_robinson_schensted",
+		py_fn!(py, inverse_robinson_schensted(insertion: LatticeWord, recording: LatticeWord)),
+	)?;
+	Ok(())
+}
+
 py_class!(pub class LatticeWords |py| {
-	data lattice_words: super::LatticeWords;
+	data lattice_words: AnyLatticeWords;
 
-	def __new__(_cls, weight: Vec<u8>) -> PyResult<Self> {
-		match super::LatticeWords::new(weight) {
+	def __new__(_cls, weight: Vec<usize>) -> PyResult<Self> {
+		match lattice_words_from_weight(weight) {
 			Ok(lattice_words) =>
 				Self::create_instance(
 					py,
@@ -52,63 +542,191 @@ py_class!(pub class LatticeWords |py| {
 		}
 	}
 
-	def maj_cdes_dict(&self) -> PyResult<HashMap<(usize, usize), usize, SeaHashBuilder>> {
-		let lattice_words = self.lattice_words(py);
+	def count(&self) -> PyResult<PyObject> {
+		let count = dispatch!(*self.lattice_words(py), ref lattice_words => lattice_words.count());
+		py.get_type::<PyLong>().call(py, (count.to_string(),), None)
+	}
 
-		{
-			let mut iter = lattice_words.weight().iter();
-			let first = iter.next();
-			for entry in iter {
-				if Some(entry) != first {
-					return Err(
-						PyErr::new_lazy_init(
-							py.get_type::<NotImplementedError>(),
-							Some("only implemented for rectangular shapes".to_py_object(py).into_object()),
-						)
-					)
-				}
-			}
+	def maj_polynomial(&self) -> PyResult<String> {
+		match dispatch!(*self.lattice_words(py), ref lattice_words => lattice_words.maj_polynomial()) {
+			Ok(polynomial) => Ok(polynomial.format_with_variables(&["q"])),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<ValueError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
 		}
+	}
+
+	// rectangles use the transfer matrix; the other shapes are enumerated on `threads` threads, by
+	// default one per CPU. Either way, `progress(done, total)` gets the number of words done out of
+	// the total from the hook length formula, where the transfer matrix reports the share of the
+	// letters placed.
+	def maj_cdes_dict(
+		&self,
+		threads: Option<usize> = None,
+		progress: Option<PyObject> = None
+	) -> PyResult<HashMap<(usize, usize), i64, SeaHashBuilder>> {
+		let polynomial = dispatch!(
+			*self.lattice_words(py), ref lattice_words => maj_cdes(py, lattice_words, threads, progress.as_ref())?
+		);
+		Ok(bivariate_dict(&polynomial))
+	}
+
+	def maj_cdes_polynomial(&self, threads: Option<usize> = None, progress: Option<PyObject> = None) -> PyResult<String> {
+		let polynomial = dispatch!(
+			*self.lattice_words(py), ref lattice_words => maj_cdes(py, lattice_words, threads, progress.as_ref())?
+		);
+		Ok(polynomial.format_with_variables(&["q", "t"]))
+	}
+
+	def distribution(
+		&self,
+		statistics: Vec<String>,
+		threads: Option<usize> = None,
+		progress: Option<PyObject> = None
+	) -> PyResult<PyDict> {
+		dispatch!(
+			*self.lattice_words(py),
+			ref lattice_words => distribution(py, lattice_words, &*statistics, threads, progress.as_ref())
+		)
+	}
+
+	def distribution_by(&self, func: PyObject) -> PyResult<PyDict> {
+		dispatch!(*self.lattice_words(py), ref lattice_words => distribution_by(py, lattice_words, &func))
+	}
+
+	// the transfer matrix computes the distribution for every shape on one thread, so `threads`,
+	// which only matters for shapes that need enumeration, is accepted for compatibility and unused.
+	// `progress(done, total)` is called as for `maj_cdes_dict`.
+	def maj_des_dict(
+		&self,
+		threads: Option<usize> = None,
+		progress: Option<PyObject> = None
+	) -> PyResult<HashMap<(usize, usize), i64, SeaHashBuilder>> {
+		let _ = threads;
+		let polynomial = dispatch!(
+			*self.lattice_words(py), ref lattice_words => maj_des(py, lattice_words, progress.as_ref())?
+		);
+		Ok(bivariate_dict(&polynomial))
+	}
+
+	def maj_des_polynomial(&self, threads: Option<usize> = None, progress: Option<PyObject> = None) -> PyResult<String> {
+		let _ = threads;
+		let polynomial = dispatch!(
+			*self.lattice_words(py), ref lattice_words => maj_des(py, lattice_words, progress.as_ref())?
+		);
+		Ok(polynomial.format_with_variables(&["q", "t"]))
+	}
+
+	def orbit_size_dict(&self, progress: Option<PyObject> = None) -> PyResult<HashMap<usize, usize, SeaHashBuilder>> {
+		dispatch!(*self.lattice_words(py), ref lattice_words => {
+			without_gil(py, word_count(lattice_words), progress.as_ref(), |report| orbit_sizes(lattice_words, report))
+		})
+	}
+
+	def cyclic_sieving(&self, progress: Option<PyObject> = None) -> PyResult<Vec<(usize, Option<i64>, usize)>> {
+		let report = dispatch!(*self.lattice_words(py), ref lattice_words => {
+			without_gil(py, word_count(lattice_words), progress.as_ref(), |report| {
+				::cyclic_sieving::cyclic_sieving_with_progress(lattice_words, report)
+			})?
+		});
 
-		let mut tracking_shape = Vec::with_capacity(lattice_words.weight().len());
-		unsafe {
-			tracking_shape.set_len(lattice_words.weight().len());
+		Ok(report.into_iter().map(|entry| (entry.d, entry.evaluation, entry.fixed_points)).collect())
+	}
+
+	def rank(&self, word: LatticeWord) -> PyResult<usize> {
+		let word = dispatch!(*word.lattice_word(py), ref word => letters(&**word));
+
+		match dispatch!(*self.lattice_words(py), ref lattice_words => rank_letters(lattice_words, &*word)) {
+			Ok(rank) => Ok(rank),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<ValueError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
 		}
+	}
 
-		Ok(generating_function(
-			lattice_words,
-			|word|
-				(
-					word.major_index(),
-					word.tableau_cyclic_descents_with_tracking_shape(&mut *tracking_shape).count(),
-				),
-		))
+	def random_element(&self, seed: Option<u64> = None) -> PyResult<LatticeWord> {
+		let word = match seed {
+			Some(seed) => {
+				let seed = [seed as usize, (seed >> 32) as usize];
+				let mut rng = StdRng::from_seed(&seed[..]);
+				dispatch!(
+					*self.lattice_words(py), ref lattice_words, wrap => wrap(lattice_words.random_element(&mut rng).into())
+				)
+			},
+			None => {
+				let mut rng = rand::thread_rng();
+				dispatch!(
+					*self.lattice_words(py), ref lattice_words, wrap => wrap(lattice_words.random_element(&mut rng).into())
+				)
+			},
+		};
+
+		LatticeWord::create_instance(py, word)
 	}
 
-	def maj_des_dict(&self) -> PyResult<HashMap<(usize, usize), usize, SeaHashBuilder>> {
-		let lattice_words = self.lattice_words(py);
+	def __len__(&self) -> PyResult<usize> {
+		let count = dispatch!(
+			*self.lattice_words(py), ref lattice_words => ::counting::hook_length_count(lattice_words.weight())
+		);
+
+		match count {
+			Some(count) => Ok(count),
+			None => Err(PyErr::new_lazy_init(
+				py.get_type::<OverflowError>(),
+				Some("too many lattice words".to_py_object(py).into_object()),
+			)),
+		}
+	}
 
-		Ok(generating_function(
-			lattice_words,
-			|word|
-				(
-					word.major_index(),
-					word.ascents().count(),
-				),
-		))
+	def __getitem__(&self, index: isize) -> PyResult<LatticeWord> {
+		let index = if index < 0 {
+			dispatch!(
+				*self.lattice_words(py), ref lattice_words => ::counting::hook_length_count(lattice_words.weight())
+			)
+				.and_then(|count| (count as isize).checked_add(index))
+				.unwrap_or(-1)
+		} else {
+			index
+		};
+
+		if index < 0 {
+			return Err(PyErr::new_lazy_init(
+				py.get_type::<IndexError>(),
+				Some("index out of range".to_py_object(py).into_object()),
+			));
+		}
+
+		let word = dispatch!(
+			*self.lattice_words(py),
+			ref lattice_words,
+			wrap => lattice_words.unrank(index as usize).map(|word| wrap(word.into()))
+		);
+
+		match word {
+			Ok(word) => LatticeWord::create_instance(py, word),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<IndexError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
+		}
 	}
 
 	def __iter__(&self) -> PyResult<LatticeWordsIter> {
 		LatticeWordsIter::create_instance(
 			py,
 			RefCell::new(
-				self.lattice_words(py).clone().into_iter()
+				dispatch!(
+					*self.lattice_words(py), ref lattice_words, wrap => wrap(lattice_words.clone().into_iter())
+				)
 			)
 		)
 	}
 
 	def __repr__(&self) -> PyResult<String> {
-		let mut iter = self.lattice_words(py).weight().iter();
+		let weight = dispatch!(*self.lattice_words(py), ref lattice_words => letters(lattice_words.weight()));
+		let mut iter = weight.iter();
 
 		Ok(if let Some(first_elt) = iter.next() {
 			let (lower_hint, _) = iter.size_hint();
@@ -132,15 +750,21 @@ py_class!(pub class LatticeWords |py| {
 });
 
 py_class!(pub class LatticeWordsIter |py| {
-	data iter: RefCell<super::LatticeWordsIter<Box<[u8]>>>;
+	data iter: RefCell<Width<
+		super::LatticeWordsIter<Box<[u8]>, u8>,
+		super::LatticeWordsIter<Box<[u16]>, u16>,
+		super::LatticeWordsIter<Box<[u32]>, u32>,
+	>>;
 
 	def __iter__(&self) -> PyResult<PyObject> {
 		Ok(self.as_object().clone_ref(py))
 	}
 
 	def __next__(&self) -> PyResult<Option<LatticeWord>> {
-		Ok(match self.iter(py).borrow_mut().next() {
-			Some(x) => Some(LatticeWord::create_instance(py, x.into())?),
+		let word = dispatch!(*self.iter(py).borrow_mut(), ref mut iter, wrap => iter.next().map(|x| wrap(x.into())));
+
+		Ok(match word {
+			Some(x) => Some(LatticeWord::create_instance(py, x)?),
 			None => None
 		})
 	}
@@ -213,11 +837,14 @@ impl<'a> FromPyObject<'a> for SliceIndex {
 	}
 }
 
-fn wordslice_getitem(
+fn wordslice_getitem<L, F>(
 	py: Python,
-	(inner, range): (&Arc<Box<[u8]>>, Range<usize>),
+	(inner, range): (&Arc<Box<[L]>>, Range<usize>),
 	index: SliceIndex,
+	wrap: F,
 	) -> PyResult<PyObject>
+	where L: Letter,
+	      F: Fn(SharedSlice<L>) -> AnySlice
 {
 	macro_rules! out_of_range {
 		() => {
@@ -251,7 +878,7 @@ fn wordslice_getitem(
 			let index = fix_index!(index);
 
 			match inner[range].get(index) {
-				Some(value) => return Ok(value.into_py_object(py).into_object()),
+				Some(value) => return Ok(value.to_usize().into_py_object(py).into_object()),
 				None => out_of_range!(),
 			}
 		},
@@ -288,16 +915,18 @@ fn wordslice_getitem(
 		}
 	};
 
-	LatticeWordSlice::create_instance(py, slice).map(|x| x.into_object())
+	LatticeWordSlice::create_instance(py, wrap(slice)).map(|x| x.into_object())
 }
 
-fn lattice_word_repr_helper(slice: &[u8], prefix: &str) -> PyResult<String> {
-	let mut iter = slice.iter();
+fn lattice_word_repr_helper<L>(slice: &[L], prefix: &str) -> PyResult<String>
+	where L: Letter
+{
+	let mut iter = slice.iter().map(|letter| letter.to_usize());
 
 	Ok(if let Some(first_elt) = iter.next() {
 		let (lower_hint, _) = iter.size_hint();
 
-		if *slice.iter().max().unwrap() < 10 {
+		if slice.iter().max().unwrap().to_usize() < 10 {
 			let mut res = String::with_capacity(prefix.len() + 1 + lower_hint);
 
 			res.push_str(prefix);
@@ -333,44 +962,88 @@ fn lattice_word_repr_helper(slice: &[u8], prefix: &str) -> PyResult<String> {
 }
 
 py_class!(pub class LatticeWordSlice |py| {
-	data slice: OwnedSlice<Arc<Box<[u8]>>>;
+	data slice: AnySlice;
 
 	def __len__(&self) -> PyResult<usize> {
-		Ok((&self.slice(py)[..]).len())
+		Ok(dispatch!(*self.slice(py), ref slice => slice.len()))
 	}
 
 	def __getitem__(&self, index: SliceIndex) -> PyResult<PyObject> {
-		wordslice_getitem(py, self.slice(py).inner(), index)
+		dispatch!(*self.slice(py), ref slice, wrap => wordslice_getitem(py, slice.inner(), index, wrap))
 	}
 
 	def __iter__(&self) -> PyResult<LatticeWordSliceIter> {
-		LatticeWordSliceIter::create_instance(py, RefCell::new(self.slice(py).clone().into_iter()))
+		LatticeWordSliceIter::create_instance(
+			py,
+			RefCell::new(dispatch!(*self.slice(py), ref slice, wrap => wrap(slice.clone().into_iter()))),
+		)
 	}
 
 	def __repr__(&self) -> PyResult<String> {
-		lattice_word_repr_helper(&self.slice(py)[..], "lattice word slice ")
+		dispatch!(*self.slice(py), ref slice => lattice_word_repr_helper(&slice[..], "lattice word slice "))
 	}
 
 	def __richcmp__(&self, other: LatticeWordSlice, op: CompareOp) -> PyResult<bool> {
-		let this = self.slice(py);
-		let other = other.slice(py);
-		Ok(match op {
-			CompareOp::Lt => this <  other,
-			CompareOp::Le => this <= other,
-			CompareOp::Eq => this == other,
-			CompareOp::Ne => this != other,
-			CompareOp::Ge => this >= other,
-			CompareOp::Gt => this >  other,
-		})
+		let this = dispatch!(*self.slice(py), ref slice => letters(&slice[..]));
+		let other = dispatch!(*other.slice(py), ref slice => letters(&slice[..]));
+		Ok(compare(this.cmp(&other), op))
+	}
+});
+
+// The word passed to the functions of `LatticeWords.distribution_by`, which reuses its buffer for
+// the next word unless the function keeps a reference to it.
+py_class!(pub class LatticeWordView |py| {
+	data word: RefCell<Vec<u32>>;
+
+	def __len__(&self) -> PyResult<usize> {
+		Ok(self.word(py).borrow().len())
+	}
+
+	def __getitem__(&self, index: isize) -> PyResult<usize> {
+		let word = self.word(py).borrow();
+		let index = if index < 0 { index + word.len() as isize } else { index };
+
+		match word.get(index as usize) {
+			Some(letter) if index >= 0 => Ok(letter.to_usize()),
+			_ => Err(PyErr::new_lazy_init(
+				py.get_type::<IndexError>(),
+				Some("index out of range".to_py_object(py).into_object()),
+			)),
+		}
+	}
+
+	def __repr__(&self) -> PyResult<String> {
+		lattice_word_repr_helper(&*self.word(py).borrow(), "lattice word view ")
+	}
+
+	def descents(&self) -> PyResult<Vec<usize>> {
+		Ok(super::LatticeWord::unchecked_new(&**self.word(py).borrow()).descents().collect())
+	}
+
+	def ascents(&self) -> PyResult<Vec<usize>> {
+		Ok(super::LatticeWord::unchecked_new(&**self.word(py).borrow()).ascents().collect())
+	}
+
+	def major_index(&self) -> PyResult<usize> {
+		Ok(super::LatticeWord::unchecked_new(&**self.word(py).borrow()).major_index())
+	}
+
+	def to_lattice_word(&self) -> PyResult<LatticeWord> {
+		match lattice_word_from_letters(letters(&*self.word(py).borrow())) {
+			Ok(lattice_word) => LatticeWord::create_instance(py, lattice_word),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<ValueError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
+		}
 	}
 });
 
 py_class!(pub class LatticeWord |py| {
-	data lattice_word: super::LatticeWord<Arc<Box<[u8]>>>;
+	data lattice_word: AnyLatticeWord;
 
-	def __new__(_cls, word: Vec<u8>) -> PyResult<Self> {
-		let word = Arc::new(word.into_boxed_slice());
-		match super::LatticeWord::new(word) {
+	def __new__(_cls, word: Vec<usize>) -> PyResult<Self> {
+		match lattice_word_from_letters(word) {
 			Ok(lattice_word) => Self::create_instance(py, lattice_word),
 			Err(s) => Err(PyErr::new_lazy_init(
 				py.get_type::<ValueError>(),
@@ -380,64 +1053,138 @@ py_class!(pub class LatticeWord |py| {
 	}
 
 	def __richcmp__(&self, other: LatticeWord, op: CompareOp) -> PyResult<bool> {
-		let this = self.lattice_word(py);
-		let other = other.lattice_word(py);
-		Ok(match op {
-			CompareOp::Lt => this <  other,
-			CompareOp::Le => this <= other,
-			CompareOp::Eq => this == other,
-			CompareOp::Ne => this != other,
-			CompareOp::Ge => this >= other,
-			CompareOp::Gt => this >  other,
-		})
+		let this = dispatch!(*self.lattice_word(py), ref lattice_word => letters(&**lattice_word));
+		let other = dispatch!(*other.lattice_word(py), ref lattice_word => letters(&**lattice_word));
+		Ok(compare(this.cmp(&other), op))
 	}
 
 	def __len__(&self) -> PyResult<usize> {
-		Ok(self.lattice_word(py).len())
+		Ok(dispatch!(*self.lattice_word(py), ref lattice_word => lattice_word.len()))
 	}
 
 	def __getitem__(&self, index: SliceIndex) -> PyResult<PyObject> {
-		let lattice_word = self.lattice_word(py);
-		wordslice_getitem(py, (lattice_word.inner(), 0..lattice_word.len()), index)
+		dispatch!(
+			*self.lattice_word(py),
+			ref lattice_word,
+			wrap => wordslice_getitem(py, (lattice_word.inner(), 0..lattice_word.len()), index, wrap)
+		)
 	}
 
 	def __repr__(&self) -> PyResult<String> {
-		lattice_word_repr_helper(&*self.lattice_word(py), "lattice word ")
+		dispatch!(*self.lattice_word(py), ref lattice_word => lattice_word_repr_helper(&**lattice_word, "lattice word "))
 	}
 
 	def __iter__(&self) -> PyResult<LatticeWordSliceIter> {
-		let lattice_word = self.lattice_word(py);
-		let range = 0..lattice_word.len();
-		let inner = self.lattice_word(py).inner().clone();
-		let slice = OwnedSlice::new(inner, range);
-		LatticeWordSliceIter::create_instance(py, RefCell::new(slice.into_iter()))
+		let iter = dispatch!(*self.lattice_word(py), ref lattice_word, wrap => {
+			let slice = OwnedSlice::new(lattice_word.inner().clone(), 0..lattice_word.len());
+			wrap(slice.into_iter())
+		});
+		LatticeWordSliceIter::create_instance(py, RefCell::new(iter))
 	}
 
 	def descents(&self) -> PyResult<ScentIter> {
-		ScentIter::create_instance(py, RefCell::new(self.lattice_word(py).clone().into_descents()))
+		ScentIter::create_instance(
+			py,
+			RefCell::new(dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.clone().into_descents()))),
+		)
 	}
 
 	def ascents(&self) -> PyResult<ScentIter> {
-		ScentIter::create_instance(py, RefCell::new(self.lattice_word(py).clone().into_ascents()))
+		ScentIter::create_instance(
+			py,
+			RefCell::new(dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.clone().into_ascents()))),
+		)
+	}
+
+	@classmethod
+	def from_tableau(_cls, rows: Vec<Vec<usize>>) -> PyResult<LatticeWord> {
+		match lattice_word_from_tableau(rows) {
+			Ok(lattice_word) => LatticeWord::create_instance(py, lattice_word),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<ValueError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
+		}
+	}
+
+	def to_tableau(&self) -> PyResult<Vec<Vec<usize>>> {
+		let tableau = dispatch!(*self.lattice_word(py), ref lattice_word => super::Tableau::from(lattice_word));
+		Ok(tableau.rows().iter().map(|row| row.to_vec()).collect())
 	}
 
 	def major_index(&self) -> PyResult<usize> {
-		Ok(self.lattice_word(py).major_index())
+		Ok(dispatch!(*self.lattice_word(py), ref lattice_word => lattice_word.major_index()))
 	}
 
 	def promotion(&self, count: usize = 1) -> PyResult<Self> {
-		match self.lattice_word(py).promotion(Some(count)) {
-			Ok(word) => Self::create_instance(py, word.into()),
+		Self::create_instance(
+			py,
+			dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.promotion(Some(count)).into())),
+		)
+	}
+
+	def promotion_order(&self) -> PyResult<usize> {
+		Ok(dispatch!(*self.lattice_word(py), ref lattice_word => lattice_word.promotion_order()))
+	}
+
+	def bender_knuth(&self, i: usize) -> PyResult<Self> {
+		let word = dispatch!(
+			*self.lattice_word(py), ref lattice_word, wrap => lattice_word.bender_knuth(i).map(|word| wrap(word.into()))
+		);
+
+		match word {
+			Ok(word) => Self::create_instance(py, word),
 			Err(s) => Err(PyErr::new_lazy_init(
-				py.get_type::<NotImplementedError>(),
+				py.get_type::<ValueError>(),
 				Some(s.to_py_object(py).into_object()),
 			)),
 		}
 	}
 
-	def promotion_order(&self) -> PyResult<usize> {
-		match self.lattice_word(py).promotion_order() {
-			Ok(order) => Ok(order),
+	def bender_knuth_product(&self, indices: Vec<usize>) -> PyResult<Self> {
+		let word = dispatch!(
+			*self.lattice_word(py),
+			ref lattice_word,
+			wrap => lattice_word.bender_knuth_product(indices).map(|word| wrap(word.into()))
+		);
+
+		match word {
+			Ok(word) => Self::create_instance(py, word),
+			Err(s) => Err(PyErr::new_lazy_init(
+				py.get_type::<ValueError>(),
+				Some(s.to_py_object(py).into_object()),
+			)),
+		}
+	}
+
+	def bender_knuth_promotion(&self) -> PyResult<Self> {
+		Self::create_instance(
+			py,
+			dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.bender_knuth_promotion().into())),
+		)
+	}
+
+	def evacuation(&self) -> PyResult<Self> {
+		Self::create_instance(
+			py,
+			dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.evacuation().into())),
+		)
+	}
+
+	def dual_evacuation(&self) -> PyResult<Self> {
+		Self::create_instance(
+			py,
+			dispatch!(*self.lattice_word(py), ref lattice_word, wrap => wrap(lattice_word.dual_evacuation().into())),
+		)
+	}
+
+	def cyclic_descents(&self) -> PyResult<CyclicDescentIter> {
+		let iter = dispatch!(
+			*self.lattice_word(py), ref lattice_word, wrap => lattice_word.clone().into_cyclic_descents().map(wrap)
+		);
+
+		match iter {
+			Ok(iter) => CyclicDescentIter::create_instance(py, RefCell::new(iter)),
 			Err(s) => Err(PyErr::new_lazy_init(
 				py.get_type::<NotImplementedError>(),
 				Some(s.to_py_object(py).into_object()),
@@ -446,7 +1193,11 @@ py_class!(pub class LatticeWord |py| {
 	}
 
 	def tableau_cyclic_descents(&self) -> PyResult<TableauCyclicDescentIter> {
-		match self.lattice_word(py).clone().into_tableau_cyclic_descents() {
+		let iter = dispatch!(
+			*self.lattice_word(py), ref lattice_word, wrap => lattice_word.clone().into_tableau_cyclic_descents().map(wrap)
+		);
+
+		match iter {
 			Ok(iter) => TableauCyclicDescentIter::create_instance(py, RefCell::new(iter)),
 			Err(s) => Err(PyErr::new_lazy_init(
 				py.get_type::<NotImplementedError>(),
@@ -457,37 +1208,61 @@ py_class!(pub class LatticeWord |py| {
 });
 
 py_class!(pub class LatticeWordSliceIter |py| {
-	data iter: RefCell<<OwnedSlice<Arc<Box<[u8]>>> as IntoIterator>::IntoIter>;
+	data iter: RefCell<Width<IntoIter<SharedSlice<u8>>, IntoIter<SharedSlice<u16>>, IntoIter<SharedSlice<u32>>>>;
 
 	def __iter__(&self) -> PyResult<PyObject> {
 		Ok(self.as_object().clone_ref(py))
 	}
 
-	def __next__(&self) -> PyResult<Option<u8>> {
-		Ok(self.iter(py).borrow_mut().next())
+	def __next__(&self) -> PyResult<Option<usize>> {
+		Ok(dispatch!(*self.iter(py).borrow_mut(), ref mut iter => iter.next().map(|letter| letter.to_usize())))
 	}
 });
 
 py_class!(pub class ScentIter |py| {
-	data iter: RefCell<super::ScentIter<Arc<Box<[u8]>>>>;
+	data iter: RefCell<Width<
+		super::ScentIter<Arc<Box<[u8]>>>,
+		super::ScentIter<Arc<Box<[u16]>>>,
+		super::ScentIter<Arc<Box<[u32]>>>,
+	>>;
 
 	def __iter__(&self) -> PyResult<PyObject> {
 		Ok(self.as_object().clone_ref(py))
 	}
 
 	def __next__(&self) -> PyResult<Option<usize>> {
-		Ok(self.iter(py).borrow_mut().next())
+		Ok(dispatch!(*self.iter(py).borrow_mut(), ref mut iter => iter.next()))
 	}
 });
 
 py_class!(pub class TableauCyclicDescentIter |py| {
-	data iter: RefCell<super::TableauCyclicDescentIter<Arc<Box<[u8]>>, Box<[u8]>>>;
+	data iter: RefCell<Width<
+		super::TableauCyclicDescentIter<Arc<Box<[u8]>>, Box<[u8]>>,
+		super::TableauCyclicDescentIter<Arc<Box<[u16]>>, Box<[u16]>>,
+		super::TableauCyclicDescentIter<Arc<Box<[u32]>>, Box<[u32]>>,
+	>>;
+
+	def __iter__(&self) -> PyResult<PyObject> {
+		Ok(self.as_object().clone_ref(py))
+	}
+
+	def __next__(&self) -> PyResult<Option<usize>> {
+		Ok(dispatch!(*self.iter(py).borrow_mut(), ref mut iter => iter.next()))
+	}
+});
+
+py_class!(pub class CyclicDescentIter |py| {
+	data iter: RefCell<Width<
+		super::CyclicDescentIter<Arc<Box<[u8]>>>,
+		super::CyclicDescentIter<Arc<Box<[u16]>>>,
+		super::CyclicDescentIter<Arc<Box<[u32]>>>,
+	>>;
 
 	def __iter__(&self) -> PyResult<PyObject> {
 		Ok(self.as_object().clone_ref(py))
 	}
 
 	def __next__(&self) -> PyResult<Option<usize>> {
-		Ok(self.iter(py).borrow_mut().next())
+		Ok(dispatch!(*self.iter(py).borrow_mut(), ref mut iter => iter.next()))
 	}
 });
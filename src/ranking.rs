@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use counting::hook_length_count;
+use letter::Letter;
+
+/// Ranks lattice words of a fixed weight in the order produced by `LatticeWordsStreamingIter`,
+/// which is colexicographic: the words are compared starting from their last letter.
+#[derive(Clone, Debug)]
+pub(crate) struct Ranker<'a, L = u8>
+	where L: 'a
+{
+	weight: &'a [L],
+	shape: Box<[L]>,
+	counts: HashMap<Box<[L]>, usize>,
+}
+
+impl<'a, L> Ranker<'a, L>
+	where L: Letter
+{
+	pub fn new(weight: &'a [L]) -> Result<Self, &'static str> {
+		if hook_length_count(weight).is_none() {
+			return Err("too many lattice words to rank");
+		}
+
+		let mut shape = Vec::with_capacity(weight.len());
+		shape.extend_from_slice(weight);
+
+		Ok(Ranker {
+			weight: weight,
+			shape: shape.into_boxed_slice(),
+			counts: HashMap::new(),
+		})
+	}
+
+	#[inline]
+	fn count(counts: &mut HashMap<Box<[L]>, usize>, shape: &[L]) -> usize {
+		if let Some(count) = counts.get(shape) {
+			return *count;
+		}
+
+		let count = hook_length_count(shape).unwrap();
+		counts.insert(shape.to_vec().into_boxed_slice(), count);
+		count
+	}
+
+	#[inline]
+	fn removable(shape: &[L], row: usize) -> bool {
+		shape[row] > L::zero() && shape.get(row + 1).map(|next| *next < shape[row]).unwrap_or(true)
+	}
+
+	/// Words ending in `letter` come after those ending in any smaller removable row, each of which
+	/// contributes the number of lattice words of the remaining shape.
+	pub fn rank(&mut self, word: &[L]) -> usize {
+		self.shape.copy_from_slice(self.weight);
+
+		let mut rank = 0;
+
+		for letter in word.iter().rev() {
+			let letter = letter.to_usize();
+
+			for row in 0..letter {
+				if Self::removable(&*self.shape, row) {
+					self.shape[row] -= L::one();
+					rank += Self::count(&mut self.counts, &*self.shape);
+					self.shape[row] += L::one();
+				}
+			}
+
+			self.shape[letter] -= L::one();
+		}
+
+		rank
+	}
+
+	/// The inverse of `rank`, writing the word into `word`; `rank` must be less than the number of
+	/// lattice words.
+	pub fn unrank(&mut self, mut rank: usize, word: &mut [L]) {
+		self.shape.copy_from_slice(self.weight);
+
+		for letter in word.iter_mut().rev() {
+			for row in 0..self.shape.len() {
+				if !Self::removable(&*self.shape, row) {
+					continue;
+				}
+
+				self.shape[row] -= L::one();
+				let count = Self::count(&mut self.counts, &*self.shape);
+
+				if rank < count {
+					*letter = L::from_usize(row);
+					break;
+				}
+
+				rank -= count;
+				self.shape[row] += L::one();
+			}
+		}
+
+		debug_assert_eq!(rank, 0);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn rank() {
+		for weight in vec![vec![3, 2], vec![4, 2, 1], vec![3, 3, 3]] {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+			let mut ranker = Ranker::new(lattice_words.weight()).unwrap();
+
+			for (index, lattice_word) in lattice_words.iter().enumerate() {
+				assert_eq!(ranker.rank(&*lattice_word), index);
+
+				let mut word = vec![0; lattice_word.len()];
+				ranker.unrank(index, &mut *word);
+				assert_eq!(&*word, &*lattice_word);
+			}
+		}
+	}
+}
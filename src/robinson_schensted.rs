@@ -0,0 +1,245 @@
+use std::mem;
+
+use full_deref::FullDeref;
+use lattice_word::{word_weight, LatticeWord};
+use letter::Letter;
+use semistandard_word::SemistandardWord;
+
+// row inserts `entry`, returning the row of the new cell
+fn insert<L>(rows: &mut Vec<Vec<L>>, mut entry: L) -> usize
+	where L: Letter
+{
+	for (index, row) in rows.iter_mut().enumerate() {
+		// the leftmost entry larger than `entry` is bumped into the next row
+		match row.iter().position(|other| *other > entry) {
+			Some(position) => entry = mem::replace(&mut row[position], entry),
+			None => {
+				row.push(entry);
+				return index;
+			},
+		}
+	}
+
+	rows.push(vec![entry]);
+	rows.len() - 1
+}
+
+// removes the last cell of the given row by reverse bumping, returning the entry that leaves the
+// first row
+fn uninsert<L>(rows: &mut Vec<Vec<L>>, row: usize) -> L
+	where L: Letter
+{
+	let mut entry = rows[row].pop().unwrap();
+
+	for index in (0..row).rev() {
+		// the rightmost entry smaller than `entry` is bumped into the previous row
+		let position = rows[index].iter().rposition(|other| *other < entry).unwrap();
+		entry = mem::replace(&mut rows[index][position], entry);
+	}
+
+	while rows.last().map(|row| row.is_empty()).unwrap_or(false) {
+		rows.pop();
+	}
+
+	entry
+}
+
+// the rows of a standard tableau given as a lattice word, with entries starting at 0
+fn standard_rows<L>(word: &[L]) -> Vec<Vec<L>>
+	where L: Letter
+{
+	let first = word.iter().min().cloned().unwrap_or(L::zero());
+	let mut rows = vec![Vec::new(); word_weight(word).len()];
+	for (entry, row) in word.iter().enumerate() {
+		rows[(*row - first).to_usize()].push(L::from_usize(entry));
+	}
+	rows
+}
+
+fn recording_rows<L>(word: &[L]) -> Vec<usize>
+	where L: Letter
+{
+	let first = word.iter().min().cloned().unwrap_or(L::zero());
+	word.iter().map(|row| (*row - first).to_usize()).collect()
+}
+
+/// The Robinson–Schensted–Knuth correspondence, which row inserts the letters of the word in turn.
+/// Returns the insertion tableau, which has the letters of the word as entries, and the recording
+/// tableau as a lattice word, whose letter `i` is the row of the cell created by the `i`th
+/// insertion.
+pub fn robinson_schensted_knuth<L>(word: &[L]) -> (SemistandardWord<Box<[L]>, Box<[L]>>, LatticeWord<Box<[L]>>)
+	where L: Letter
+{
+	let mut rows = Vec::new();
+	let recording: Vec<_> = word.iter().map(|entry| L::from_usize(insert(&mut rows, *entry))).collect();
+
+	let shape: Vec<_> = rows.iter().map(|row| L::from_usize(row.len())).collect();
+	(
+		SemistandardWord::unchecked_new(rows.concat().into_boxed_slice(), shape.into_boxed_slice()),
+		LatticeWord::unchecked_new(recording.into_boxed_slice()),
+	)
+}
+
+/// Recovers the word from its insertion and recording tableaux, which need to have the same shape.
+pub fn inverse_robinson_schensted_knuth<T, U, V, L>(
+	insertion: &SemistandardWord<T, U>,
+	recording: &LatticeWord<V>,
+	) -> Result<Vec<L>, &'static str>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      V: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	let shape: Vec<_> = insertion.shape().iter().cloned().filter(|row| *row != L::zero()).collect();
+	if shape != word_weight(&**recording) {
+		return Err("tableaux do not have the same shape");
+	}
+
+	let mut rows: Vec<_> = (0..shape.len()).map(|index| insertion.row(index).to_vec()).collect();
+	let mut word: Vec<_> = recording_rows(&**recording).into_iter().rev().map(|row| uninsert(&mut rows, row)).collect();
+	word.reverse();
+
+	Ok(word)
+}
+
+/// The Robinson–Schensted correspondence for a permutation of `0, ..., n - 1` in one-line notation.
+/// Both tableaux are standard and given as lattice words. The descents of the permutation are the
+/// tableau descents of the recording tableau, i.e. its ascents as a word, and those of the inverse
+/// permutation are the tableau descents of the insertion tableau.
+pub fn robinson_schensted<L>(permutation: &[L]) -> Result<(LatticeWord<Box<[L]>>, LatticeWord<Box<[L]>>), &'static str>
+	where L: Letter
+{
+	let mut seen = vec![false; permutation.len()];
+	for entry in permutation {
+		match seen.get_mut(entry.to_usize()) {
+			Some(seen) if !*seen => *seen = true,
+			_ => return Err("word is not a permutation"),
+		}
+	}
+
+	let (insertion, recording) = robinson_schensted_knuth(permutation);
+
+	let mut rows = vec![L::zero(); permutation.len()];
+	for (row_index, _) in insertion.shape().iter().enumerate() {
+		for entry in insertion.row(row_index) {
+			rows[entry.to_usize()] = L::from_usize(row_index);
+		}
+	}
+
+	Ok((LatticeWord::unchecked_new(rows.into_boxed_slice()), recording))
+}
+
+/// Recovers the permutation from its insertion and recording tableaux, which need to have the
+/// same shape.
+pub fn inverse_robinson_schensted<T, U, L>(
+	insertion: &LatticeWord<T>,
+	recording: &LatticeWord<U>,
+	) -> Result<Vec<L>, &'static str>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	if word_weight(&**insertion) != word_weight(&**recording) {
+		return Err("tableaux do not have the same shape");
+	}
+
+	let mut rows = standard_rows(&**insertion);
+	let mut permutation: Vec<_> = recording_rows(&**recording)
+		.into_iter()
+		.rev()
+		.map(|row| uninsert(&mut rows, row))
+		.collect();
+	permutation.reverse();
+
+	Ok(permutation)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cmp::Ordering;
+
+	use super::*;
+	use lattice_word::ScentIter;
+	use lattice_words::LatticeWords;
+
+	fn permutations(n: u8) -> Vec<Vec<u8>> {
+		if n == 0 {
+			return vec![Vec::new()];
+		}
+
+		let mut larger = Vec::new();
+		for smaller in permutations(n - 1) {
+			for position in 0..n as usize {
+				let mut permutation = smaller.clone();
+				permutation.insert(position, n - 1);
+				larger.push(permutation);
+			}
+		}
+		larger
+	}
+
+	#[test]
+	fn example() {
+		let (insertion, recording) = robinson_schensted(&[2u8, 0, 3, 1]).unwrap();
+		// P = 0 1 / 2 3 and Q = 0 2 / 1 3
+		assert_eq!(&*insertion, &[0, 0, 1, 1]);
+		assert_eq!(&*recording, &[0, 1, 0, 1]);
+		assert_eq!(inverse_robinson_schensted(&insertion, &recording), Ok(vec![2, 0, 3, 1]));
+
+		assert!(robinson_schensted(&[0u8, 2, 2]).is_err());
+		assert!(robinson_schensted(&[0u8, 3, 1]).is_err());
+		assert!(inverse_robinson_schensted(&insertion, &LatticeWord::new(&[0u8, 0, 0, 1][..]).unwrap()).is_err());
+	}
+
+	#[test]
+	fn bijection_and_descents() {
+		for n in 0..7 {
+			let mut pairs = Vec::new();
+
+			for permutation in permutations(n) {
+				let (insertion, recording) = robinson_schensted(&*permutation).unwrap();
+				assert_eq!(inverse_robinson_schensted(&insertion, &recording).as_ref(), Ok(&permutation));
+
+				let mut inverse = vec![0; permutation.len()];
+				for (index, entry) in permutation.iter().enumerate() {
+					inverse[*entry as usize] = index as u8;
+				}
+
+				let descents: Vec<_> = ScentIter::new(&*permutation, Ordering::Less).collect();
+				let inverse_descents: Vec<_> = ScentIter::new(&*inverse, Ordering::Less).collect();
+				assert_eq!(recording.ascents().collect::<Vec<_>>(), descents);
+				assert_eq!(insertion.ascents().collect::<Vec<_>>(), inverse_descents);
+
+				// the insertion tableau of the inverse is the recording tableau
+				assert_eq!(robinson_schensted(&*inverse).unwrap(), (recording.clone(), insertion.clone()));
+
+				pairs.push((insertion, recording));
+			}
+
+			pairs.sort();
+			pairs.dedup();
+			assert_eq!(pairs.len(), (1..n as usize + 1).product());
+		}
+	}
+
+	#[test]
+	fn words() {
+		let word = [1u8, 2, 0, 1, 1, 0];
+		let (insertion, recording) = robinson_schensted_knuth(&word);
+		assert_eq!(insertion.shape(), &[3, 2, 1]);
+		assert_eq!(&*insertion, &[0, 0, 1, 1, 1, 2]);
+		assert!(SemistandardWord::new(insertion.inner().clone(), insertion.shape().to_vec()).is_ok());
+		assert_eq!(&*recording, &[0, 0, 1, 1, 0, 2]);
+		assert_eq!(inverse_robinson_schensted_knuth(&insertion, &recording), Ok(word.to_vec()));
+
+		// every pair of a semistandard and a standard tableau of the same shape comes from a word
+		let shape = vec![3, 1];
+		for recording in LatticeWords::new(shape.clone()).unwrap().iter() {
+			let insertion = SemistandardWord::new(vec![0, 1, 1, 2], shape.clone()).unwrap();
+			let word = inverse_robinson_schensted_knuth(&insertion, &recording).unwrap();
+			let (other_insertion, other_recording) = robinson_schensted_knuth(&*word);
+			assert_eq!(&*other_insertion, &*insertion);
+			assert_eq!(other_recording, recording);
+		}
+	}
+}
@@ -0,0 +1,84 @@
+use rand::Rng;
+
+use letter::Letter;
+
+// the number of cells of the hook of (row, column) other than the cell itself, split into the arm
+// and the leg
+#[inline]
+fn arm_and_leg<L>(shape: &[L], row: usize, column: usize) -> (usize, usize)
+	where L: Letter
+{
+	let arm = shape[row].to_usize() - column - 1;
+	let leg = shape[row + 1..]
+		.iter()
+		.take_while(|other_row| other_row.to_usize() > column)
+		.count();
+	(arm, leg)
+}
+
+/// Fills `word` with a uniformly random lattice word of the given weight using the
+/// Greene–Nijenhuis–Wilf hook walk: each walk starts at a uniformly random cell and jumps to a
+/// uniformly random cell of its hook until it reaches a corner, which then holds the largest
+/// remaining entry.
+pub(crate) fn hook_walk<R, L>(weight: &[L], rng: &mut R, word: &mut [L])
+	where R: Rng,
+	      L: Letter
+{
+	let mut shape = weight.to_vec();
+
+	for (size, letter) in word.iter_mut().enumerate().rev() {
+		let mut cell = rng.gen_range(0, size + 1);
+		let mut row = 0;
+		while cell >= shape[row].to_usize() {
+			cell -= shape[row].to_usize();
+			row += 1;
+		}
+		let mut column = cell;
+
+		loop {
+			let (arm, leg) = arm_and_leg(&*shape, row, column);
+			if arm + leg == 0 {
+				break;
+			}
+
+			let step = rng.gen_range(0, arm + leg);
+			if step < arm {
+				column += step + 1;
+			} else {
+				row += step - arm + 1;
+			}
+		}
+
+		*letter = L::from_usize(row);
+		shape[row] -= L::one();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use rand::{SeedableRng, XorShiftRng};
+
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn uniform() {
+		let lattice_words = LatticeWords::new(vec![3, 2, 1]).unwrap();
+		let count = lattice_words.iter().count();
+		let samples = 1000 * count;
+
+		let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+		let mut frequencies = vec![0; count];
+		let mut word = vec![0; 6];
+
+		for _ in 0..samples {
+			hook_walk(lattice_words.weight(), &mut rng, &mut *word);
+			let word = ::lattice_word::LatticeWord::new(&*word).unwrap();
+			frequencies[lattice_words.rank(&word).unwrap()] += 1;
+		}
+
+		for frequency in frequencies {
+			assert!(frequency > 850 && frequency < 1150);
+		}
+	}
+}
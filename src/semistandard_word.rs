@@ -0,0 +1,256 @@
+use std::ops::Deref;
+
+use full_deref::FullDeref;
+use lattice_word::LatticeWord;
+use letter::Letter;
+use partition::is_partition;
+
+/// A semistandard tableau of the given shape, stored as its rows from top to bottom, each from left
+/// to right. Entries start at 0, like the letters of lattice words.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct SemistandardWord<T, U> {
+	inner: T,
+	shape: U,
+}
+
+impl<T, U> SemistandardWord<T, U>
+	where T: FullDeref<Target = [u8]>,
+	      U: FullDeref<Target = [u8]>
+{
+	#[inline]
+	pub fn new(inner: T, shape: U) -> Result<Self, &'static str> { Self::from_letters(inner, shape) }
+}
+
+impl<T, U, L> SemistandardWord<T, U>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_letters(inner: T, shape: U) -> Result<Self, &'static str> {
+		{
+			let word = inner.full_deref();
+			let shape = shape.full_deref();
+
+			if !is_partition(shape) {
+				return Err("shape is not a partition");
+			}
+			if shape.iter().fold(0, |partial, row| partial + row.to_usize()) != word.len() {
+				return Err("word does not have the size of the shape");
+			}
+
+			let mut offset = 0;
+			let mut previous_row: &[L] = &[];
+			for row in shape {
+				let row = &word[offset..offset + row.to_usize()];
+				if row.windows(2).any(|pair| pair[1] < pair[0]) {
+					return Err("rows are not weakly increasing");
+				}
+				if row.iter().zip(previous_row.iter()).any(|(entry, above)| entry <= above) {
+					return Err("columns are not strictly increasing");
+				}
+				offset += row.len();
+				previous_row = row;
+			}
+		}
+		Ok(Self::unchecked_new(inner, shape))
+	}
+
+	#[inline]
+	pub fn unchecked_new(inner: T, shape: U) -> Self { SemistandardWord { inner: inner, shape: shape } }
+
+	#[inline]
+	pub fn shape(&self) -> &[L] { self.shape.full_deref() }
+
+	/// The entries of the given row.
+	pub fn row(&self, index: usize) -> &[L] {
+		let shape = self.shape();
+		let offset = shape[..index].iter().fold(0, |partial, row| partial + row.to_usize());
+		&self.inner.full_deref()[offset..offset + shape[index].to_usize()]
+	}
+
+	/// The sum of the entries, which the principal specialization of the Schur function counts.
+	#[inline]
+	pub fn entry_sum(&self) -> usize { self.iter().fold(0, |partial, entry| partial + entry.to_usize()) }
+
+	/// The number of entries equal to each value up to the largest entry.
+	pub fn content(&self) -> Vec<L> {
+		let mut content = vec![L::zero(); self.iter().max().map(|max| max.to_usize() + 1).unwrap_or(0)];
+		for entry in self.iter() {
+			content[entry.to_usize()] += L::one();
+		}
+		content
+	}
+
+	/// The standard tableau obtained by numbering equal entries from left to right, as a lattice
+	/// word. Its descents are the positions where the next entry lies in a lower row, so the
+	/// statistics of `LatticeWord` apply to it.
+	pub fn standardization(&self) -> LatticeWord<Box<[L]>> {
+		let mut cells = Vec::with_capacity(self.len());
+		for (row_index, _) in self.shape().iter().enumerate() {
+			for (column, entry) in self.row(row_index).iter().enumerate() {
+				cells.push((*entry, column, row_index));
+			}
+		}
+		cells.sort();
+
+		LatticeWord::unchecked_new(
+			cells.into_iter().map(|(_, _, row)| L::from_usize(row)).collect::<Vec<_>>().into_boxed_slice(),
+		)
+	}
+
+	/// The Bender–Knuth involution that exchanges the numbers of free entries equal to `entry` and
+	/// `entry + 1` in every row, where an `entry` is free unless an `entry + 1` lies below it and
+	/// vice versa.
+	pub fn bender_knuth(&self, entry: L) -> SemistandardWord<Box<[L]>, Box<[L]>> {
+		let shape = self.shape();
+		let mut word = self.inner.full_deref().to_vec();
+		let next = entry + L::one();
+
+		let mut offset = 0;
+		for (row_index, row) in shape.iter().enumerate() {
+			let row = row.to_usize();
+			let above = if row_index > 0 { Some(self.row(row_index - 1)) } else { None };
+			let below = if row_index + 1 < shape.len() { Some(self.row(row_index + 1)) } else { None };
+
+			let mut free = Vec::new();
+			for column in 0..row {
+				let value = word[offset + column];
+				let fixed = if value == entry {
+					below.and_then(|below| below.get(column)).map(|below| *below == next).unwrap_or(false)
+				} else if value == next {
+					above.map(|above| above[column] == entry).unwrap_or(false)
+				} else {
+					continue;
+				};
+				if !fixed {
+					free.push(offset + column);
+				}
+			}
+
+			// the free entries are consecutive, so exchanging their numbers keeps the row sorted
+			let smaller = free.iter().filter(|index| word[**index] == entry).count();
+			let larger = free.len() - smaller;
+			for (position, index) in free.into_iter().enumerate() {
+				word[index] = if position < larger { entry } else { next };
+			}
+
+			offset += row;
+		}
+
+		SemistandardWord::unchecked_new(word.into_boxed_slice(), shape.to_vec().into_boxed_slice())
+	}
+
+	/// Promotion for entries at most `max_entry`, which applies the Bender–Knuth involutions for
+	/// `max_entry - 1` down to `0` in turn, `count` times (once by default). On standard tableaux
+	/// this agrees with `LatticeWord::promotion`.
+	pub fn promotion(&self, max_entry: L, count: Option<usize>) -> SemistandardWord<Box<[L]>, Box<[L]>> {
+		let mut word = SemistandardWord::from(self);
+		for _ in 0..count.unwrap_or(1) {
+			word = word.promotion_step(max_entry);
+		}
+		word
+	}
+
+	/// The smallest positive number of promotions that give back the word.
+	pub fn promotion_order(&self, max_entry: L) -> usize {
+		let mut order = 1;
+		let mut word = self.promotion(max_entry, None);
+		while &*word != &**self {
+			word = word.promotion_step(max_entry);
+			order += 1;
+		}
+		order
+	}
+
+	fn promotion_step(&self, max_entry: L) -> SemistandardWord<Box<[L]>, Box<[L]>> {
+		let mut word = SemistandardWord::from(self);
+		for entry in (0..max_entry.to_usize()).rev() {
+			word = word.bender_knuth(L::from_usize(entry));
+		}
+		word
+	}
+
+	#[inline]
+	pub fn inner(&self) -> &T {
+		&self.inner
+	}
+}
+
+impl<T, U, L> Deref for SemistandardWord<T, U>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	type Target = [L];
+
+	#[inline]
+	fn deref(&self) -> &Self::Target { self.inner.full_deref() }
+}
+
+impl<'a, T, U, L> From<&'a SemistandardWord<T, U>> for SemistandardWord<Box<[L]>, Box<[L]>>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	#[inline]
+	fn from(x: &'a SemistandardWord<T, U>) -> Self {
+		SemistandardWord::unchecked_new(
+			x.inner.full_deref().to_vec().into_boxed_slice(),
+			x.shape.full_deref().to_vec().into_boxed_slice(),
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn validation() {
+		assert!(SemistandardWord::new(vec![0, 0, 1, 0, 2], vec![3, 2]).is_err());
+		assert!(SemistandardWord::new(vec![0, 0, 2, 1, 2], vec![3, 2]).is_ok());
+		assert!(SemistandardWord::new(vec![0, 1, 0, 1, 2], vec![3, 2]).is_err());
+		assert!(SemistandardWord::new(vec![0, 0, 1, 1], vec![3, 2]).is_err());
+
+		let word = SemistandardWord::new(vec![0, 0, 2, 1, 2], vec![3, 2]).unwrap();
+		assert_eq!(word.row(1), &[1, 2]);
+		assert_eq!(word.entry_sum(), 5);
+		assert_eq!(word.content(), vec![2, 1, 2]);
+		assert_eq!(&*word.standardization(), &[0, 0, 1, 1, 0]);
+	}
+
+	#[test]
+	fn bender_knuth() {
+		// the second row is fixed, the first one has one free 0 and three free 1s
+		let word = SemistandardWord::new(vec![0, 0, 1, 1, 1, 1, 2], vec![5, 2]).unwrap();
+		let image = word.bender_knuth(0);
+		assert_eq!(&*image, &[0, 0, 0, 0, 1, 1, 2]);
+		assert_eq!(image.bender_knuth(0), SemistandardWord::from(&word));
+	}
+
+	#[test]
+	fn standard_promotion() {
+		// on standard tableaux, promotion agrees with that of lattice words
+		for weight in vec![vec![3, 2], vec![4, 2, 1], vec![3, 3]] {
+			let size = weight.iter().fold(0, |partial, row| partial + row) as u8;
+
+			for lattice_word in LatticeWords::new(weight.clone()).unwrap().iter() {
+				let to_tableau = |lattice_word: &[u8]| {
+					let mut rows = vec![Vec::new(); weight.len()];
+					for (entry, row) in lattice_word.iter().enumerate() {
+						rows[*row as usize].push(entry as u8);
+					}
+					SemistandardWord::new(rows.concat(), weight.clone()).unwrap()
+				};
+
+				let tableau = to_tableau(&*lattice_word);
+				assert_eq!(&*tableau.standardization(), &*lattice_word);
+				assert_eq!(
+					tableau.promotion(size - 1, None),
+					SemistandardWord::from(&to_tableau(&*lattice_word.promotion(None)))
+				);
+			}
+		}
+	}
+}
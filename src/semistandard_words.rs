@@ -0,0 +1,318 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+use num_bigint::BigUint;
+
+use counting::{hook_content_big_count, q_hook_content_polynomial};
+use full_deref::FullDeref;
+use lattice_words::histogram_polynomial;
+use letter::Letter;
+use partition::Partition;
+use polynomial::Polynomial;
+use semistandard_word::SemistandardWord;
+
+/// The semistandard tableaux of the given shape with entries at most `max_entry`.
+#[derive(Clone, Debug)]
+pub struct SemistandardWords<L = u8> {
+	shape: Box<[L]>,
+	max_entry: L,
+}
+
+/// Iterates over the tableaux in lexicographic order of their rows.
+#[derive(Clone, Debug)]
+pub struct SemistandardWordsStreamingIter<T, L = u8> {
+	shape: T,
+	max_entry: L,
+	first_pass: bool,
+	current: Box<[L]>,
+	// the row and column of every cell, and the upper bound imposed by the rows below it
+	cells: Box<[(usize, usize, L)]>,
+	offsets: Box<[usize]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SemistandardWordsIter<T, L = u8> {
+	inner: SemistandardWordsStreamingIter<T, L>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SemistandardPromotionOrbitsIter<'a, L = u8>
+	where L: 'a
+{
+	inner: SemistandardWordsStreamingIter<&'a [L], L>,
+	// the words of orbits that have been started but not yet reached by `inner`
+	pending: HashSet<Box<[L]>>,
+}
+
+impl SemistandardWords {
+	#[inline]
+	pub fn new(shape: Vec<u8>, max_entry: u8) -> Result<Self, &'static str> { Self::from_shape(shape, max_entry) }
+}
+
+impl<L> SemistandardWords<L>
+	where L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_shape(shape: Vec<L>, max_entry: L) -> Result<Self, &'static str> {
+		let shape = Partition::from_parts(shape)?;
+		Ok(SemistandardWords { shape: shape.into_parts(), max_entry: max_entry })
+	}
+
+	#[inline]
+	pub fn shape(&self) -> &[L] { &*self.shape }
+
+	#[inline]
+	pub fn max_entry(&self) -> L { self.max_entry }
+
+	#[inline]
+	pub fn streaming_iter(&self) -> SemistandardWordsStreamingIter<&[L], L> {
+		SemistandardWordsStreamingIter::new(&*self.shape, self.max_entry)
+	}
+
+	#[inline]
+	pub fn into_streaming_iter(self) -> SemistandardWordsStreamingIter<Box<[L]>, L> {
+		SemistandardWordsStreamingIter::new(self.shape, self.max_entry)
+	}
+
+	#[inline]
+	pub fn iter(&self) -> SemistandardWordsIter<&[L], L> {
+		SemistandardWordsIter { inner: self.streaming_iter() }
+	}
+
+	/// The number of tableaux, by the hook content formula.
+	#[inline]
+	pub fn count(&self) -> BigUint { hook_content_big_count(&*self.shape, self.max_entry.to_usize() + 1) }
+
+	/// The generating function by the sum of the entries, i.e. the principal specialization
+	/// `s_λ(1, q, ..., q^max_entry)`, by the q-hook content formula.
+	#[inline]
+	pub fn entry_sum_polynomial(&self) -> Result<Polynomial, &'static str> {
+		q_hook_content_polynomial(&*self.shape, self.max_entry.to_usize() + 1)
+			.ok_or("too many semistandard tableaux for the coefficients to fit")
+	}
+
+	/// Sums the monomials with the exponents returned by `f` over all tableaux.
+	pub fn generating_function<F, E>(&self, mut f: F) -> Polynomial
+		where F: FnMut(SemistandardWord<&[L], &[L]>) -> E,
+		      E: AsRef<[usize]> + Eq + Hash
+	{
+		let mut counts = HashMap::new();
+
+		let mut iter = self.streaming_iter();
+		while let Some(word) = iter.next() {
+			*counts.entry(f(word)).or_insert(0) += 1;
+		}
+
+		histogram_polynomial(counts)
+	}
+
+	/// Splits the tableaux into orbits of Bender–Knuth promotion, yielding the first tableau of each
+	/// orbit (in iteration order) together with the size of the orbit.
+	#[inline]
+	pub fn promotion_orbits(&self) -> SemistandardPromotionOrbitsIter<L> {
+		SemistandardPromotionOrbitsIter { inner: self.streaming_iter(), pending: HashSet::new() }
+	}
+}
+
+impl<L> IntoIterator for SemistandardWords<L>
+	where L: Letter
+{
+	type Item = SemistandardWord<Box<[L]>, Box<[L]>>;
+	type IntoIter = SemistandardWordsIter<Box<[L]>, L>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter { SemistandardWordsIter { inner: self.into_streaming_iter() } }
+}
+
+impl<T, L> SemistandardWordsStreamingIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	fn new(shape: T, max_entry: L) -> Self {
+		let mut cells = Vec::new();
+		let mut offsets = Vec::new();
+		let possible = shape.full_deref().len() <= max_entry.to_usize() + 1;
+
+		{
+			let shape = shape.full_deref();
+			for (row_index, row) in shape.iter().enumerate() {
+				offsets.push(cells.len());
+				for column in 0..row.to_usize() {
+					let below = shape[row_index + 1..].iter().take_while(|other| other.to_usize() > column).count();
+					let bound = if possible { max_entry - L::from_usize(below) } else { L::zero() };
+					cells.push((row_index, column, bound));
+				}
+			}
+		}
+
+		let mut iter = SemistandardWordsStreamingIter {
+			shape: shape,
+			max_entry: max_entry,
+			first_pass: possible,
+			current: vec![L::zero(); if possible { cells.len() } else { 0 }].into_boxed_slice(),
+			cells: cells.into_boxed_slice(),
+			offsets: offsets.into_boxed_slice(),
+		};
+		iter.fill_smallest(0);
+		iter
+	}
+
+	// the smallest entry allowed by the cells to the left and above
+	#[inline]
+	fn lower_bound(&self, index: usize) -> L {
+		let (row, column, _) = self.cells[index];
+		let mut bound = L::zero();
+		if column > 0 {
+			bound = self.current[index - 1];
+		}
+		if row > 0 {
+			let above = self.current[self.offsets[row - 1] + column] + L::one();
+			if above > bound {
+				bound = above;
+			}
+		}
+		bound
+	}
+
+	fn fill_smallest(&mut self, start: usize) {
+		for index in start..self.current.len() {
+			self.current[index] = self.lower_bound(index);
+		}
+	}
+
+	pub fn next(&mut self) -> Option<SemistandardWord<&[L], &[L]>> {
+		if self.first_pass {
+			self.first_pass = false;
+
+			return Some(SemistandardWord::unchecked_new(&*self.current, self.shape.full_deref()));
+		}
+
+		// find the last entry that can be increased
+		let index = {
+			let cells = &self.cells;
+			self.current
+				.iter()
+				.enumerate()
+				.rposition(|(index, entry)| *entry < cells[index].2)
+		};
+
+		match index {
+			Some(index) => {
+				self.current[index] += L::one();
+				self.fill_smallest(index + 1);
+				Some(SemistandardWord::unchecked_new(&*self.current, self.shape.full_deref()))
+			},
+			None => {
+				// make later calls return `None` right away
+				self.current = Vec::new().into_boxed_slice();
+				None
+			},
+		}
+	}
+}
+
+impl<T, L> Iterator for SemistandardWordsIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	type Item = SemistandardWord<Box<[L]>, Box<[L]>>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> { self.inner.next().map(|x| Self::Item::from(&x)) }
+}
+
+impl<T, L> FusedIterator for SemistandardWordsIter<T, L> where Self: Iterator {}
+
+impl<'a, L> Iterator for SemistandardPromotionOrbitsIter<'a, L>
+	where L: Letter
+{
+	type Item = (SemistandardWord<Box<[L]>, Box<[L]>>, usize);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let max_entry = self.inner.max_entry;
+
+		loop {
+			let word = SemistandardWord::from(&self.inner.next()?);
+
+			if self.pending.remove(&*word) {
+				continue;
+			}
+
+			let mut size = 1;
+			let mut current = word.promotion(max_entry, None);
+
+			while current != word {
+				self.pending.insert(current.to_vec().into_boxed_slice());
+				size += 1;
+				current = current.promotion(max_entry, None);
+			}
+
+			return Some((word, size));
+		}
+	}
+}
+
+impl<'a, L> FusedIterator for SemistandardPromotionOrbitsIter<'a, L> where L: Letter {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn basic_case() {
+		let words: Vec<_> = SemistandardWords::new(vec![2, 1], 2)
+			.unwrap()
+			.iter()
+			.map(|word| word.to_vec())
+			.collect();
+
+		assert_eq!(
+			words,
+			vec![
+				vec![0, 0, 1],
+				vec![0, 0, 2],
+				vec![0, 1, 1],
+				vec![0, 1, 2],
+				vec![0, 2, 1],
+				vec![0, 2, 2],
+				vec![1, 1, 2],
+				vec![1, 2, 2],
+			]
+		);
+
+		assert!(SemistandardWords::new(vec![1, 1, 1], 1).unwrap().iter().next().is_none());
+
+		let empty = SemistandardWords::new(vec![], 3).unwrap();
+		assert_eq!(empty.iter().count(), 1);
+	}
+
+	#[test]
+	fn closed_forms() {
+		for (shape, max_entry) in vec![(vec![2, 1], 2), (vec![3, 3], 3), (vec![4, 2, 1], 3), (vec![2, 2, 2], 4)] {
+			let words = SemistandardWords::new(shape, max_entry).unwrap();
+
+			for word in words.iter() {
+				assert!(SemistandardWord::new(&*word, words.shape()).is_ok());
+			}
+
+			assert_eq!(BigUint::from(words.iter().count()), words.count());
+			assert_eq!(
+				words.entry_sum_polynomial(),
+				Ok(words.generating_function(|word| [word.entry_sum()]))
+			);
+		}
+	}
+
+	#[test]
+	fn promotion_orbits() {
+		let words = SemistandardWords::new(vec![2, 2], 3).unwrap();
+		let orbits: Vec<_> = words.promotion_orbits().collect();
+
+		assert_eq!(orbits.iter().fold(0, |partial, &(_, size)| partial + size), 20);
+		for &(ref word, size) in &orbits {
+			assert_eq!(word.promotion_order(3), size);
+			assert_eq!(4 % size, 0);
+		}
+	}
+}
@@ -0,0 +1,342 @@
+use std::cmp::{self, Ordering};
+use std::ops::Deref;
+
+use full_deref::FullDeref;
+use lattice_word::{LatticeWord, ScentIter};
+use letter::Letter;
+use partition::{is_partition, Partition};
+
+/// A lattice word of skew shape `λ/μ`: letter `i` is the row of entry `i + 1` of a standard tableau
+/// of shape `λ/μ`, so the lattice condition is checked starting from the subweight `μ`.
+///
+/// Unlike straight lattice words, letters are absolute row indices and are never shifted.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct SkewLatticeWord<T, U> {
+	inner: T,
+	inner_shape: U,
+}
+
+impl<T, U> SkewLatticeWord<T, U>
+	where T: FullDeref<Target = [u8]>,
+	      U: FullDeref<Target = [u8]>
+{
+	#[inline]
+	pub fn new(inner: T, inner_shape: U) -> Result<Self, &'static str> { Self::from_letters(inner, inner_shape) }
+}
+
+impl<T, U, L> SkewLatticeWord<T, U>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_letters(inner: T, inner_shape: U) -> Result<Self, &'static str> {
+		{
+			let word = inner.full_deref();
+			let shape = inner_shape.full_deref();
+
+			if !is_partition(shape) {
+				return Err("inner shape is not a partition");
+			}
+
+			let rows = cmp::max(
+				shape.len(),
+				word.iter().max().map(|max| max.to_usize() + 1).unwrap_or(0),
+			);
+			let mut subweight: Vec<_> = shape.iter().map(|row| row.to_usize()).collect();
+			subweight.resize(rows, 0);
+
+			for entry in word {
+				let entry = entry.to_usize();
+				subweight[entry] += 1;
+				if entry > 0 && subweight[entry] > subweight[entry - 1] {
+					return Err("word is not a lattice word");
+				}
+			}
+		}
+		Ok(Self::unchecked_new(inner, inner_shape))
+	}
+
+	#[inline]
+	pub fn unchecked_new(inner: T, inner_shape: U) -> Self {
+		SkewLatticeWord { inner: inner, inner_shape: inner_shape }
+	}
+
+	#[inline]
+	pub fn inner_shape(&self) -> &[L] { self.inner_shape.full_deref() }
+
+	/// The shape `λ`, i.e. the inner shape plus the weight of the word.
+	pub fn outer_shape(&self) -> Vec<L> {
+		let mut shape = self.inner_shape().to_vec();
+		for entry in self.inner.full_deref() {
+			let entry = entry.to_usize();
+			if entry >= shape.len() {
+				shape.resize(entry + 1, L::zero());
+			}
+			shape[entry] += L::one();
+		}
+		shape
+	}
+
+	#[inline]
+	pub fn descents(&self) -> ScentIter<&[L]> {
+		ScentIter::new(self.inner.full_deref(), Ordering::Less)
+	}
+
+	#[inline]
+	pub fn into_descents(self) -> ScentIter<T> { ScentIter::new(self.inner, Ordering::Less) }
+
+	#[inline]
+	pub fn ascents(&self) -> ScentIter<&[L]> {
+		ScentIter::new(self.inner.full_deref(), Ordering::Greater)
+	}
+
+	#[inline]
+	pub fn into_ascents(self) -> ScentIter<T> { ScentIter::new(self.inner, Ordering::Greater) }
+
+	#[inline]
+	pub fn major_index(&self) -> usize { self.ascents().fold(0, |partial, x| partial + x) }
+
+	/// The rows of the inner corners of the inner shape, into which a cell can be slid.
+	pub fn inner_corners(&self) -> Vec<usize> {
+		let shape = self.inner_shape();
+		(0..shape.len())
+			.filter(|row| {
+				shape[*row] != L::zero() && shape.get(row + 1).map(|next| *next < shape[*row]).unwrap_or(true)
+			})
+			.collect()
+	}
+
+	/// The rows of the cells that can be added to the outer shape, into which a cell can be slid
+	/// back.
+	pub fn outer_corners(&self) -> Vec<usize> {
+		let shape = self.outer_shape();
+		(0..shape.len() + 1)
+			.filter(|row| *row == 0 || shape[row - 1] > shape.get(*row).cloned().unwrap_or(L::zero()))
+			.collect()
+	}
+
+	// the entries of every row by column, with `None` for the cells of the inner shape
+	fn grid(&self) -> Vec<Vec<Option<usize>>> {
+		let mut grid: Vec<Vec<_>> = self.inner_shape().iter().map(|row| vec![None; row.to_usize()]).collect();
+		for (entry, row) in self.iter().enumerate() {
+			let row = row.to_usize();
+			if row >= grid.len() {
+				grid.resize(row + 1, Vec::new());
+			}
+			grid[row].push(Some(entry));
+		}
+		grid
+	}
+
+	fn from_grid(grid: Vec<Vec<Option<usize>>>, len: usize) -> SkewLatticeWord<Box<[L]>, Box<[L]>> {
+		let mut word = vec![L::zero(); len];
+		let mut inner_shape = Vec::with_capacity(grid.len());
+
+		for (row_index, row) in grid.into_iter().enumerate() {
+			inner_shape.push(L::from_usize(row.iter().take_while(|cell| cell.is_none()).count()));
+			for entry in row.into_iter().filter_map(|cell| cell) {
+				word[entry] = L::from_usize(row_index);
+			}
+		}
+
+		// sliding keeps the inner shape a partition
+		let inner_shape = Partition::from_parts(inner_shape).unwrap();
+		SkewLatticeWord::unchecked_new(word.into_boxed_slice(), inner_shape.into_parts())
+	}
+
+	/// A forward jeu de taquin slide into the inner corner in the given row: the hole repeatedly
+	/// swaps with the smaller of its neighbours to the right and below until it leaves the outer
+	/// shape.
+	pub fn slide(&self, row: usize) -> Result<SkewLatticeWord<Box<[L]>, Box<[L]>>, &'static str> {
+		if !self.inner_corners().contains(&row) {
+			return Err("not an inner corner");
+		}
+
+		let mut grid = self.grid();
+		let mut hole_row = row;
+		let mut hole_column = grid[row].iter().take_while(|cell| cell.is_none()).count() - 1;
+
+		loop {
+			let right = grid[hole_row].get(hole_column + 1).cloned().and_then(|cell| cell);
+			let below = grid
+				.get(hole_row + 1)
+				.and_then(|next| next.get(hole_column))
+				.cloned()
+				.and_then(|cell| cell);
+
+			let (next_row, next_column) = match (right, below) {
+				(Some(right), Some(below)) if below < right => (hole_row + 1, hole_column),
+				(Some(_), _) => (hole_row, hole_column + 1),
+				(None, Some(_)) => (hole_row + 1, hole_column),
+				(None, None) => break,
+			};
+
+			grid[hole_row][hole_column] = grid[next_row][next_column];
+			hole_row = next_row;
+			hole_column = next_column;
+		}
+
+		grid[hole_row].pop();
+		Ok(Self::from_grid(grid, self.len()))
+	}
+
+	/// A reverse jeu de taquin slide from the outer corner in the given row: the hole repeatedly
+	/// swaps with the larger of its neighbours to the left and above until it joins the inner shape.
+	pub fn reverse_slide(&self, row: usize) -> Result<SkewLatticeWord<Box<[L]>, Box<[L]>>, &'static str> {
+		if !self.outer_corners().contains(&row) {
+			return Err("not an outer corner");
+		}
+
+		let mut grid = self.grid();
+		if row == grid.len() {
+			grid.push(Vec::new());
+		}
+		let mut hole_row = row;
+		let mut hole_column = grid[row].len();
+		grid[row].push(None);
+
+		loop {
+			let left = if hole_column > 0 { grid[hole_row][hole_column - 1] } else { None };
+			let above = if hole_row > 0 { grid[hole_row - 1][hole_column] } else { None };
+
+			let (next_row, next_column) = match (left, above) {
+				(Some(left), Some(above)) if above > left => (hole_row - 1, hole_column),
+				(Some(_), _) => (hole_row, hole_column - 1),
+				(None, Some(_)) => (hole_row - 1, hole_column),
+				(None, None) => break,
+			};
+
+			grid[hole_row][hole_column] = grid[next_row][next_column];
+			grid[next_row][next_column] = None;
+			hole_row = next_row;
+			hole_column = next_column;
+		}
+
+		Ok(Self::from_grid(grid, self.len()))
+	}
+
+	/// The straight tableau obtained by sliding into inner corners until the inner shape is empty,
+	/// which does not depend on the order of the slides.
+	pub fn rectification(&self) -> LatticeWord<Box<[L]>> {
+		let mut word = SkewLatticeWord::from(self);
+		while let Some(row) = word.inner_corners().pop() {
+			word = word.slide(row).unwrap();
+		}
+		LatticeWord::unchecked_new(word.inner)
+	}
+
+	#[inline]
+	pub fn inner(&self) -> &T {
+		&self.inner
+	}
+}
+
+impl<T, U, L> Deref for SkewLatticeWord<T, U>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	type Target = [L];
+
+	#[inline]
+	fn deref(&self) -> &Self::Target { self.inner.full_deref() }
+}
+
+impl<'a, T, U, L> From<&'a SkewLatticeWord<T, U>> for SkewLatticeWord<Box<[L]>, Box<[L]>>
+	where T: FullDeref<Target = [L]>,
+	      U: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	#[inline]
+	fn from(x: &'a SkewLatticeWord<T, U>) -> Self {
+		SkewLatticeWord::unchecked_new(
+			x.inner.full_deref().to_vec().into_boxed_slice(),
+			x.inner_shape.full_deref().to_vec().into_boxed_slice(),
+		)
+	}
+}
+
+impl<'a, T, L> From<&'a LatticeWord<T>> for SkewLatticeWord<Box<[L]>, Box<[L]>>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	/// The lattice word as a skew word with empty inner shape, with its letters shifted to start at 0.
+	#[inline]
+	fn from(x: &'a LatticeWord<T>) -> Self {
+		let first = x.first().cloned().unwrap_or(L::zero());
+		SkewLatticeWord::unchecked_new(
+			x.iter().map(|letter| *letter - first).collect::<Vec<_>>().into_boxed_slice(),
+			Vec::new().into_boxed_slice(),
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn validation() {
+		assert!(SkewLatticeWord::new(vec![1, 0], vec![1]).is_ok());
+		assert!(SkewLatticeWord::new(vec![1, 0], vec![]).is_err());
+		assert!(SkewLatticeWord::new(vec![2, 1], vec![2, 1]).is_ok());
+		assert!(SkewLatticeWord::new(vec![1, 1], vec![1]).is_err());
+		assert!(SkewLatticeWord::new(vec![0], vec![1, 2]).is_err());
+
+		// the letters are not shifted like those of straight lattice words
+		assert!(SkewLatticeWord::new(vec![1, 1], vec![]).is_err());
+
+		let word = SkewLatticeWord::new(vec![1, 0, 2, 0, 1], vec![2, 1]).unwrap();
+		assert_eq!(word.outer_shape(), vec![4, 3, 1]);
+		assert_eq!(word.descents().collect::<Vec<_>>(), vec![1, 3]);
+		assert_eq!(word.ascents().collect::<Vec<_>>(), vec![2, 4]);
+		assert_eq!(word.major_index(), 6);
+	}
+
+	#[test]
+	fn jeu_de_taquin() {
+		// the skew tableau
+		//     . . 1 4
+		//     . 2 5
+		//     3
+		let word = SkewLatticeWord::new(vec![0, 1, 2, 0, 1], vec![2, 1]).unwrap();
+		assert_eq!(word.inner_corners(), vec![0, 1]);
+		assert_eq!(word.outer_corners(), vec![0, 1, 2, 3]);
+		assert!(word.slide(2).is_err());
+		assert!(word.reverse_slide(4).is_err());
+
+		//     . . 1 4
+		//     2 5
+		//     3
+		let slid = word.slide(1).unwrap();
+		assert_eq!(&*slid, &[0, 1, 2, 0, 1]);
+		assert_eq!(slid.inner_shape(), &[2]);
+
+		//     . 1 4
+		//     . 2 5
+		//     3
+		let slid = word.slide(0).unwrap();
+		assert_eq!(&*slid, &[0, 1, 2, 0, 1]);
+		assert_eq!(slid.inner_shape(), &[1, 1]);
+		assert_eq!(slid.reverse_slide(0), Ok(SkewLatticeWord::from(&word)));
+		assert_eq!(slid.reverse_slide(3).unwrap().inner_shape(), &[1, 1, 1]);
+
+		// 1 4
+		// 2 5
+		// 3
+		let rectification = word.rectification();
+		assert_eq!(&*rectification, &[0, 1, 2, 0, 1]);
+
+		for lattice_word in LatticeWords::new(vec![3, 2, 1]).unwrap().iter() {
+			let skew = SkewLatticeWord::from(&lattice_word);
+			assert_eq!(skew.rectification(), lattice_word);
+
+			for row in skew.outer_corners() {
+				let reverse = skew.reverse_slide(row).unwrap();
+				assert!(SkewLatticeWord::new(&*reverse, reverse.inner_shape()).is_ok());
+				assert_eq!(reverse.rectification(), lattice_word);
+			}
+		}
+	}
+}
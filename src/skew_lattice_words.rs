@@ -0,0 +1,238 @@
+use std::iter::FusedIterator;
+
+use full_deref::FullDeref;
+use letter::Letter;
+use partition::Partition;
+use skew_lattice_word::SkewLatticeWord;
+
+/// The lattice words of skew shape `λ/μ`, i.e. the standard tableaux of that shape.
+#[derive(Clone, Debug)]
+pub struct SkewLatticeWords<L = u8> {
+	outer_shape: Box<[L]>,
+	inner_shape: Box<[L]>,
+}
+
+/// Iterates over the words in lexicographic order.
+#[derive(Clone, Debug)]
+pub struct SkewLatticeWordsStreamingIter<T, L = u8> {
+	outer_shape: T,
+	inner_shape: T,
+	first_pass: bool,
+	current: Box<[L]>,
+	subweight: Box<[L]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SkewLatticeWordsIter<T, L = u8> {
+	inner: SkewLatticeWordsStreamingIter<T, L>,
+}
+
+impl SkewLatticeWords {
+	#[inline]
+	pub fn new(outer_shape: Vec<u8>, inner_shape: Vec<u8>) -> Result<Self, &'static str> {
+		Self::from_shapes(outer_shape, inner_shape)
+	}
+}
+
+impl<L> SkewLatticeWords<L>
+	where L: Letter
+{
+	/// The same as `new`, for letters wider than `u8`.
+	pub fn from_shapes(outer_shape: Vec<L>, inner_shape: Vec<L>) -> Result<Self, &'static str> {
+		let outer_shape = Partition::from_parts(outer_shape)?;
+		let inner_shape = Partition::from_parts(inner_shape)?;
+		if !outer_shape.contains(&inner_shape) {
+			return Err("inner shape is not contained in the outer shape");
+		}
+		// the largest letter plus one must fit, as for `LatticeWords`
+		if outer_shape.len() > L::max_usize() {
+			return Err("too many rows for the letter type");
+		}
+		let mut inner_shape = inner_shape.into_parts().into_vec();
+		inner_shape.resize(outer_shape.len(), L::zero());
+		Ok(SkewLatticeWords {
+			outer_shape: outer_shape.into_parts(),
+			inner_shape: inner_shape.into_boxed_slice(),
+		})
+	}
+
+	#[inline]
+	pub fn outer_shape(&self) -> &[L] { &*self.outer_shape }
+
+	/// The inner shape, padded with zeros to the length of the outer shape.
+	#[inline]
+	pub fn inner_shape(&self) -> &[L] { &*self.inner_shape }
+
+	#[inline]
+	pub fn streaming_iter(&self) -> SkewLatticeWordsStreamingIter<&[L], L> {
+		SkewLatticeWordsStreamingIter::new(&*self.outer_shape, &*self.inner_shape)
+	}
+
+	#[inline]
+	pub fn into_streaming_iter(self) -> SkewLatticeWordsStreamingIter<Box<[L]>, L> {
+		SkewLatticeWordsStreamingIter::new(self.outer_shape, self.inner_shape)
+	}
+
+	#[inline]
+	pub fn iter(&self) -> SkewLatticeWordsIter<&[L], L> {
+		SkewLatticeWordsIter { inner: self.streaming_iter() }
+	}
+}
+
+impl<L> IntoIterator for SkewLatticeWords<L>
+	where L: Letter
+{
+	type Item = SkewLatticeWord<Box<[L]>, Box<[L]>>;
+	type IntoIter = SkewLatticeWordsIter<Box<[L]>, L>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter { SkewLatticeWordsIter { inner: self.into_streaming_iter() } }
+}
+
+// fills `word` with the smallest letters that keep `subweight` a partition inside `outer_shape`;
+// this never gets stuck since every partition between the two shapes can be grown to the outer one
+#[inline]
+fn fill_smallest<L>(word: &mut [L], subweight: &mut [L], outer_shape: &[L])
+	where L: Letter
+{
+	for letter in word {
+		let row = (0..outer_shape.len())
+			.find(|row| {
+				subweight[*row] < outer_shape[*row] && (*row == 0 || subweight[*row] < subweight[*row - 1])
+			})
+			.unwrap();
+		subweight[row] += L::one();
+		*letter = L::from_usize(row);
+	}
+}
+
+impl<T, L> SkewLatticeWordsStreamingIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	fn new(outer_shape: T, inner_shape: T) -> Self {
+		let size = outer_shape
+			.full_deref()
+			.iter()
+			.zip(inner_shape.full_deref().iter())
+			.fold(0, |partial, (outer, inner)| partial + (*outer - *inner).to_usize());
+		let mut current = vec![L::zero(); size].into_boxed_slice();
+		let mut subweight = inner_shape.full_deref().to_vec().into_boxed_slice();
+		fill_smallest(&mut *current, &mut *subweight, outer_shape.full_deref());
+
+		SkewLatticeWordsStreamingIter {
+			outer_shape: outer_shape,
+			inner_shape: inner_shape,
+			first_pass: true,
+			current: current,
+			subweight: subweight,
+		}
+	}
+
+	pub fn next(&mut self) -> Option<SkewLatticeWord<&[L], &[L]>> {
+		if self.first_pass {
+			self.first_pass = false;
+
+			return Some(SkewLatticeWord::unchecked_new(&*self.current, self.inner_shape.full_deref()));
+		}
+
+		let outer_shape = self.outer_shape.full_deref();
+
+		// find the last letter that can be increased, removing the letters after it
+		let mut index = self.current.len();
+		loop {
+			if index == 0 {
+				// make later calls return `None` right away
+				self.current = Vec::new().into_boxed_slice();
+				return None;
+			}
+			index -= 1;
+
+			let letter = self.current[index].to_usize();
+			self.subweight[letter] -= L::one();
+
+			let next_row = {
+				let subweight = &self.subweight;
+				(letter + 1..outer_shape.len()).find(|row| {
+					subweight[*row] < outer_shape[*row] && subweight[*row] < subweight[*row - 1]
+				})
+			};
+
+			if let Some(row) = next_row {
+				self.subweight[row] += L::one();
+				self.current[index] = L::from_usize(row);
+				break;
+			}
+		}
+
+		fill_smallest(&mut self.current[index + 1..], &mut *self.subweight, outer_shape);
+
+		Some(SkewLatticeWord::unchecked_new(&*self.current, self.inner_shape.full_deref()))
+	}
+}
+
+impl<T, L> Iterator for SkewLatticeWordsIter<T, L>
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	type Item = SkewLatticeWord<Box<[L]>, Box<[L]>>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> { self.inner.next().map(|x| Self::Item::from(&x)) }
+}
+
+impl<T, L> FusedIterator for SkewLatticeWordsIter<T, L> where Self: Iterator {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn basic_case() {
+		let words: Vec<_> = SkewLatticeWords::new(vec![3, 2], vec![1])
+			.unwrap()
+			.iter()
+			.map(|word| word.to_vec())
+			.collect();
+
+		assert_eq!(
+			words,
+			vec![
+				vec![0, 0, 1, 1],
+				vec![0, 1, 0, 1],
+				vec![0, 1, 1, 0],
+				vec![1, 0, 0, 1],
+				vec![1, 0, 1, 0],
+			]
+		);
+
+		for word in SkewLatticeWords::new(vec![4, 3, 1], vec![2, 1]).unwrap().iter() {
+			assert!(SkewLatticeWord::new(&*word, vec![2, 1]).is_ok());
+			assert_eq!(word.outer_shape(), vec![4, 3, 1]);
+		}
+	}
+
+	#[test]
+	fn disconnected_and_straight_shapes() {
+		// two disjoint rows of length 2
+		let disconnected = SkewLatticeWords::new(vec![4, 2], vec![2]).unwrap();
+		assert_eq!(disconnected.iter().count(), 6);
+
+		let straight = LatticeWords::new(vec![4, 2, 1]).unwrap();
+		let skew = SkewLatticeWords::new(vec![4, 2, 1], vec![]).unwrap();
+		let mut straight_words: Vec<_> = straight.iter().map(|word| (word.to_vec(), word.major_index())).collect();
+		let skew_words: Vec<_> = skew.iter().map(|word| (word.to_vec(), word.major_index())).collect();
+		straight_words.sort();
+		assert_eq!(straight_words, skew_words);
+
+		let empty = SkewLatticeWords::new(vec![3, 1], vec![3, 1]).unwrap();
+		let mut iter = empty.streaming_iter();
+		assert_eq!(iter.next().map(|word| word.len()), Some(0));
+		assert!(iter.next().is_none());
+		assert!(iter.next().is_none());
+
+		assert!(SkewLatticeWords::new(vec![3, 1], vec![2, 2]).is_err());
+		assert!(SkewLatticeWords::new(vec![3, 1], vec![1, 1, 1]).is_err());
+	}
+}
@@ -0,0 +1,271 @@
+use cyclic_descents::CyclicDescentExtension;
+use lattice_word::LatticeWord;
+use lattice_words::WordStatistics;
+use letter::Letter;
+
+/// A statistic on lattice words, i.e. on standard tableaux, for `LatticeWords::joint_distribution`.
+pub trait Statistic<L = u8>: Sync
+	where L: Letter
+{
+	fn value(&self, word: LatticeWord<&[L]>) -> usize;
+
+	/// The same as `value`, where `statistics` are those of the word from
+	/// `LatticeWords::statistics_streaming_iter`, so the statistic need not read the whole word.
+	#[inline]
+	fn value_with(&self, word: LatticeWord<&[L]>, statistics: &WordStatistics) -> usize {
+		let _ = statistics;
+		self.value(word)
+	}
+}
+
+/// The sum of the descents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MajorIndex;
+
+/// The sum of `n - i` over the descents `i`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CoMajorIndex;
+
+/// The number of descents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Descents;
+
+/// The number of cyclic descents, for the shapes that have a `CyclicDescentExtension`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CyclicDescents {
+	extension: CyclicDescentExtension,
+}
+
+/// The number of descents `i > 1` such that `i - 1` is not a descent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Peaks;
+
+/// The number of pairs of letters where the larger one comes first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Inversions;
+
+/// The first letter of the reverse reading word, which reads the rows from top to bottom and each
+/// from right to left, i.e. the largest entry of the first row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FirstLetter;
+
+/// The row of the entry `n`, counted from 0 even for words whose letters start above 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LastLetter;
+
+/// The built-in statistic with the given name: `maj`, `comaj`, `des`, `cdes`, `peaks`, `inv`,
+/// `first` or `last`. Cyclic descents depend on the weight of the words.
+pub fn statistic_by_name<L>(name: &str, weight: &[L]) -> Result<Box<Statistic<L>>, &'static str>
+	where L: Letter
+{
+	Ok(match name {
+		"maj" => Box::new(MajorIndex),
+		"comaj" => Box::new(CoMajorIndex),
+		"des" => Box::new(Descents),
+		"cdes" => Box::new(CyclicDescents::for_weight(weight)?),
+		"peaks" => Box::new(Peaks),
+		"inv" => Box::new(Inversions),
+		"first" => Box::new(FirstLetter),
+		"last" => Box::new(LastLetter),
+		_ => return Err("unknown statistic"),
+	})
+}
+
+impl CyclicDescents {
+	#[inline]
+	pub fn for_weight<L>(weight: &[L]) -> Result<Self, &'static str>
+		where L: Letter
+	{
+		Ok(CyclicDescents { extension: CyclicDescentExtension::for_weight(weight)? })
+	}
+}
+
+impl<L> Statistic<L> for MajorIndex
+	where L: Letter
+{
+	#[inline]
+	fn value(&self, word: LatticeWord<&[L]>) -> usize { word.major_index() }
+
+	#[inline]
+	fn value_with(&self, _: LatticeWord<&[L]>, statistics: &WordStatistics) -> usize { statistics.major_index }
+}
+
+impl<L> Statistic<L> for CoMajorIndex
+	where L: Letter
+{
+	#[inline]
+	fn value(&self, word: LatticeWord<&[L]>) -> usize {
+		word.ascents().fold(0, |partial, descent| partial + word.len() - descent)
+	}
+
+	#[inline]
+	fn value_with(&self, word: LatticeWord<&[L]>, statistics: &WordStatistics) -> usize {
+		statistics.descents * word.len() - statistics.major_index
+	}
+}
+
+impl<L> Statistic<L> for Descents
+	where L: Letter
+{
+	#[inline]
+	fn value(&self, word: LatticeWord<&[L]>) -> usize { word.ascents().count() }
+
+	#[inline]
+	fn value_with(&self, _: LatticeWord<&[L]>, statistics: &WordStatistics) -> usize { statistics.descents }
+}
+
+impl<L> Statistic<L> for CyclicDescents
+	where L: Letter
+{
+	#[inline]
+	fn value(&self, word: LatticeWord<&[L]>) -> usize { word.cyclic_descents_with(&self.extension).count() }
+}
+
+impl<L> Statistic<L> for Peaks
+	where L: Letter
+{
+	fn value(&self, word: LatticeWord<&[L]>) -> usize {
+		let mut peaks = 0;
+		let mut previous = 0;
+		for descent in word.ascents() {
+			if descent > 1 && descent != previous + 1 {
+				peaks += 1;
+			}
+			previous = descent;
+		}
+		peaks
+	}
+}
+
+impl<L> Statistic<L> for Inversions
+	where L: Letter
+{
+	fn value(&self, word: LatticeWord<&[L]>) -> usize {
+		let first = match word.first() {
+			Some(first) => *first,
+			None => return 0,
+		};
+
+		// the number of letters so far in each row
+		let mut counts = Vec::new();
+		let mut inversions = 0;
+		for letter in word.iter() {
+			let row = (*letter - first).to_usize();
+			if row >= counts.len() {
+				counts.resize(row + 1, 0);
+			}
+			inversions += counts[row + 1..].iter().fold(0, |partial, count| partial + count);
+			counts[row] += 1;
+		}
+		inversions
+	}
+}
+
+impl<L> Statistic<L> for FirstLetter
+	where L: Letter
+{
+	fn value(&self, word: LatticeWord<&[L]>) -> usize {
+		match word.first() {
+			Some(first) => word.iter().rposition(|letter| letter == first).unwrap() + 1,
+			None => 0,
+		}
+	}
+}
+
+impl<L> Statistic<L> for LastLetter
+	where L: Letter
+{
+	#[inline]
+	fn value(&self, word: LatticeWord<&[L]>) -> usize {
+		match (word.first(), word.last()) {
+			(Some(first), Some(last)) => (*last - *first).to_usize(),
+			_ => 0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn values() {
+		// the tableau 1 2 5 / 3 6 / 4 has the descents 2, 3 and 5
+		let word = LatticeWord::new(&[0, 0, 1, 2, 0, 1][..]).unwrap();
+
+		assert_eq!(Statistic::<u8>::value(&MajorIndex, word), 10);
+		assert_eq!(Statistic::<u8>::value(&CoMajorIndex, word), 8);
+		assert_eq!(Statistic::<u8>::value(&Descents, word), 3);
+		assert_eq!(Statistic::<u8>::value(&Peaks, word), 2);
+		assert_eq!(Statistic::<u8>::value(&Inversions, word), 3);
+		assert_eq!(Statistic::<u8>::value(&FirstLetter, word), 5);
+		assert_eq!(Statistic::<u8>::value(&LastLetter, word), 1);
+
+		// the letters of lattice words may start above 0
+		let shifted = LatticeWord::new(&[2, 2, 3, 2, 3, 4][..]).unwrap();
+		assert_eq!(Statistic::<u8>::value(&FirstLetter, shifted), 4);
+		assert_eq!(Statistic::<u8>::value(&LastLetter, shifted), 2);
+		assert_eq!(Statistic::<u8>::value(&Inversions, shifted), 1);
+	}
+
+	#[test]
+	fn values_with_statistics() {
+		let lattice_words = LatticeWords::new(vec![4, 3, 1]).unwrap();
+		let statistics: Vec<&Statistic> = vec![&MajorIndex, &CoMajorIndex, &Descents, &Peaks, &FirstLetter];
+
+		let mut iter = lattice_words.statistics_streaming_iter();
+		while let Some((word, word_statistics)) = iter.next() {
+			for statistic in &statistics {
+				assert_eq!(statistic.value_with(word, &word_statistics), statistic.value(word));
+			}
+		}
+	}
+
+	#[test]
+	fn by_name() {
+		let weight: &[u8] = &[3, 3];
+		for name in vec!["maj", "comaj", "des", "cdes", "peaks", "inv", "first", "last"] {
+			assert!(statistic_by_name(name, weight).is_ok());
+		}
+
+		assert!(statistic_by_name("height", weight).is_err());
+		assert!(statistic_by_name::<u8>("cdes", &[3, 1]).is_err());
+	}
+
+	#[test]
+	fn joint_distributions() {
+		let lattice_words = LatticeWords::new(vec![4, 4]).unwrap();
+		let cyclic_descents = statistic_by_name("cdes", lattice_words.weight()).unwrap();
+
+		assert_eq!(
+			lattice_words.joint_distribution(&[&MajorIndex, &Descents]),
+			lattice_words.maj_des_polynomial().unwrap()
+		);
+		assert_eq!(
+			lattice_words.joint_distribution(&[&MajorIndex, &*cyclic_descents]),
+			lattice_words.maj_cdes_polynomial().unwrap()
+		);
+
+		// cyclic descent sets are closed under rotation, so each of 1, ..., n is a cyclic descent
+		// of the same number of tableaux
+		let size = 8;
+		let cdes = lattice_words.joint_distribution(&[&*cyclic_descents]);
+		let des = lattice_words.joint_distribution(&[&Descents]);
+		let total = |polynomial: &::polynomial::Polynomial| {
+			polynomial.terms().fold(0, |partial, (exponents, coefficient)| {
+				partial + coefficient * exponents.get(0).cloned().unwrap_or(0) as i64
+			})
+		};
+		assert_eq!(total(&cdes) * (size - 1), total(&des) * size);
+
+		// the first row of a tableau of shape (4, 4) ends in 4, ..., 7
+		let first = lattice_words.joint_distribution(&[&FirstLetter]);
+		assert_eq!(first.terms().map(|(exponents, _)| exponents.to_vec()).collect::<Vec<_>>(), vec![
+			vec![4],
+			vec![5],
+			vec![6],
+			vec![7],
+		]);
+	}
+}
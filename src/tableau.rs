@@ -0,0 +1,186 @@
+use full_deref::FullDeref;
+use lattice_word::LatticeWord;
+use letter::Letter;
+
+/// A standard Young tableau with entries `0, ..., n - 1`, stored as its rows. Entry `i` lies in
+/// the row given by letter `i` of the corresponding lattice word.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Tableau {
+	rows: Box<[Box<[usize]>]>,
+	// the row and column of every entry
+	cells: Box<[(usize, usize)]>,
+}
+
+impl Tableau {
+	pub fn new(mut rows: Vec<Vec<usize>>) -> Result<Self, &'static str> {
+		while rows.last().map(|row| row.is_empty()).unwrap_or(false) {
+			rows.pop();
+		}
+
+		if rows.windows(2).any(|pair| pair[1].len() > pair[0].len()) {
+			return Err("shape is not a partition");
+		}
+
+		let size = rows.iter().fold(0, |partial, row| partial + row.len());
+		let mut cells = vec![None; size];
+		for (row_index, row) in rows.iter().enumerate() {
+			for (column, entry) in row.iter().enumerate() {
+				match cells.get_mut(*entry) {
+					Some(cell) if cell.is_none() => *cell = Some((row_index, column)),
+					_ => return Err("entries are not 0, ..., n - 1"),
+				}
+			}
+		}
+
+		if rows.iter().any(|row| row.windows(2).any(|pair| pair[1] < pair[0])) {
+			return Err("rows are not increasing");
+		}
+		if rows.windows(2).any(|pair| pair[1].iter().zip(pair[0].iter()).any(|(entry, above)| entry < above)) {
+			return Err("columns are not increasing");
+		}
+
+		Ok(Tableau {
+			rows: rows.into_iter().map(|row| row.into_boxed_slice()).collect::<Vec<_>>().into_boxed_slice(),
+			cells: cells.into_iter().map(Option::unwrap).collect::<Vec<_>>().into_boxed_slice(),
+		})
+	}
+
+	#[inline]
+	pub fn rows(&self) -> &[Box<[usize]>] { &*self.rows }
+
+	#[inline]
+	pub fn row(&self, index: usize) -> &[usize] { &*self.rows[index] }
+
+	/// The number of entries.
+	#[inline]
+	pub fn len(&self) -> usize { self.cells.len() }
+
+	#[inline]
+	pub fn shape(&self) -> Vec<usize> { self.rows.iter().map(|row| row.len()).collect() }
+
+	/// The row and column of the given entry.
+	#[inline]
+	pub fn cell(&self, entry: usize) -> Option<(usize, usize)> { self.cells.get(entry).cloned() }
+
+	/// The entry in the given row and column.
+	#[inline]
+	pub fn entry(&self, row: usize, column: usize) -> Option<usize> {
+		self.rows.get(row).and_then(|row| row.get(column)).cloned()
+	}
+
+	/// The transposed tableau, whose rows are the columns of this one.
+	pub fn conjugate(&self) -> Tableau {
+		let mut rows = vec![Vec::new(); self.rows.first().map(|row| row.len()).unwrap_or(0)];
+		for row in self.rows.iter() {
+			for (column, entry) in row.iter().enumerate() {
+				rows[column].push(*entry);
+			}
+		}
+
+		Tableau {
+			rows: rows.into_iter().map(|row| row.into_boxed_slice()).collect::<Vec<_>>().into_boxed_slice(),
+			cells: self.cells.iter().map(|&(row, column)| (column, row)).collect::<Vec<_>>().into_boxed_slice(),
+		}
+	}
+}
+
+impl<'a, T, L> From<&'a LatticeWord<T>> for Tableau
+	where T: FullDeref<Target = [L]>,
+	      L: Letter
+{
+	fn from(x: &'a LatticeWord<T>) -> Self {
+		// the first letter is the smallest one
+		let first = x.first().cloned().unwrap_or(L::zero());
+
+		let mut rows = Vec::new();
+		let mut cells = Vec::with_capacity(x.len());
+		for (entry, letter) in x.iter().enumerate() {
+			let row = (*letter - first).to_usize();
+			if row == rows.len() {
+				rows.push(Vec::new());
+			}
+			cells.push((row, rows[row].len()));
+			rows[row].push(entry);
+		}
+
+		Tableau {
+			rows: rows.into_iter().map(|row| row.into_boxed_slice()).collect::<Vec<_>>().into_boxed_slice(),
+			cells: cells.into_boxed_slice(),
+		}
+	}
+}
+
+impl<'a, L> From<&'a Tableau> for LatticeWord<Box<[L]>>
+	where L: Letter
+{
+	#[inline]
+	fn from(x: &'a Tableau) -> Self {
+		let letters: Vec<_> = x.cells.iter().map(|&(row, _)| L::from_usize(row)).collect();
+		LatticeWord::unchecked_new(letters.into_boxed_slice())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn validation() {
+		assert!(Tableau::new(vec![vec![0, 1, 3], vec![2, 4]]).is_ok());
+		assert!(Tableau::new(vec![vec![0, 1, 3], vec![2, 4], vec![]]).is_ok());
+		assert!(Tableau::new(vec![vec![0, 1], vec![2, 3, 4]]).is_err());
+		assert!(Tableau::new(vec![vec![0, 1, 3], vec![2, 2]]).is_err());
+		assert!(Tableau::new(vec![vec![0, 1, 5], vec![2, 4]]).is_err());
+		assert!(Tableau::new(vec![vec![0, 3, 1], vec![2, 4]]).is_err());
+		assert!(Tableau::new(vec![vec![0, 2, 3], vec![1, 4]]).is_ok());
+		assert!(Tableau::new(vec![vec![0, 2, 4], vec![1, 3]]).is_ok());
+		assert!(Tableau::new(vec![vec![0, 3, 4], vec![1, 2]]).is_err());
+		assert!(Tableau::new(vec![]).is_ok());
+	}
+
+	#[test]
+	fn queries() {
+		let lattice_word = LatticeWord::new(vec![0, 0, 1, 0, 1, 2]).unwrap();
+		let tableau = Tableau::from(&lattice_word);
+
+		assert_eq!(tableau, Tableau::new(vec![vec![0, 1, 3], vec![2, 4], vec![5]]).unwrap());
+		assert_eq!(tableau.len(), 6);
+		assert_eq!(tableau.shape(), vec![3, 2, 1]);
+		assert_eq!(tableau.cell(4), Some((1, 1)));
+		assert_eq!(tableau.cell(6), None);
+		assert_eq!(tableau.entry(0, 2), Some(3));
+		assert_eq!(tableau.entry(2, 1), None);
+		assert_eq!(tableau.conjugate(), Tableau::new(vec![vec![0, 2, 5], vec![1, 4], vec![3]]).unwrap());
+
+		// the letters of lattice words may start above 0
+		assert_eq!(Tableau::from(&LatticeWord::new(vec![2, 2, 3, 2, 3, 4]).unwrap()), tableau);
+	}
+
+	#[test]
+	fn conversions() {
+		for weight in vec![vec![3, 2], vec![4, 2, 1], vec![3, 3, 3]] {
+			for lattice_word in LatticeWords::new(weight.clone()).unwrap().iter() {
+				let tableau = Tableau::from(&lattice_word);
+				assert_eq!(tableau.shape(), weight.iter().map(|row| *row as usize).collect::<Vec<_>>());
+				assert_eq!(LatticeWord::from(&tableau), lattice_word);
+				let rows = tableau.rows().iter().map(|row| row.to_vec()).collect();
+				assert_eq!(Tableau::new(rows), Ok(tableau.clone()));
+
+				for entry in 0..tableau.len() {
+					let (row, column) = tableau.cell(entry).unwrap();
+					assert_eq!(tableau.entry(row, column), Some(entry));
+				}
+
+				// transposing turns the descents into the remaining positions
+				let conjugate = tableau.conjugate();
+				assert_eq!(conjugate.conjugate(), tableau);
+				let conjugate_word: LatticeWord<Box<[u8]>> = LatticeWord::from(&conjugate);
+				assert_eq!(
+					conjugate_word.ascents().chain(lattice_word.ascents()).fold(0, |partial, _| partial + 1),
+					tableau.len() - 1
+				);
+			}
+		}
+	}
+}
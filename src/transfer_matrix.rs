@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use lattice_words::histogram_polynomial;
+use letter::Letter;
+use partition::Partition;
+use polynomial::Polynomial;
+
+// a prefix of a lattice word, up to what the statistics need to know about it
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Prefix<S> {
+	subweight: Box<[usize]>,
+	last_row: usize,
+	state: S,
+}
+
+// the hole of `TableauCyclicDescentIter`, and whether the last step moved it down
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Hole {
+	row: usize,
+	column: usize,
+	cyclic_descent: bool,
+}
+
+// the number of prefixes with each value of `[maj, ascents]`
+type Distribution = HashMap<[usize; 2], i64>;
+
+fn add_distribution(
+	target: &mut Distribution,
+	source: &Distribution,
+	maj: usize,
+	ascents: usize,
+	) -> Result<(), &'static str>
+{
+	for (exponents, count) in source {
+		let entry = target.entry([exponents[0] + maj, exponents[1] + ascents]).or_insert(0);
+		*entry = entry.checked_add(*count).ok_or("too many lattice words for the coefficients to fit")?;
+	}
+	Ok(())
+}
+
+// Builds the lattice words letter by letter, merging the prefixes that agree in their subweight,
+// their last letter and `state`, which is updated by `step` from the row and the new length of
+// that row. The second variable counts the ascents, i.e. the tableau descents, plus `bonus` of
+// the final state. `progress` gets the number of letters placed after each layer, and returning
+// false from it stops with an error.
+fn joint_distribution<L, S, F, G, P>(
+	weight: &[L],
+	start: S,
+	mut step: F,
+	mut bonus: G,
+	mut progress: P,
+	) -> Result<Polynomial, &'static str>
+	where L: Letter,
+	      S: Clone + Eq + Hash,
+	      F: FnMut(&S, usize, usize) -> S,
+	      G: FnMut(&S) -> usize,
+	      P: FnMut(usize) -> bool
+{
+	let weight: Vec<_> = weight.iter().map(|row| row.to_usize()).collect();
+	let size = weight.iter().fold(0, |partial, row| partial + row);
+
+	if size == 0 {
+		return Ok(Polynomial::constant(1));
+	}
+
+	let mut subweight = vec![0; weight.len()];
+	subweight[0] = 1;
+
+	let mut layer = HashMap::new();
+	let mut initial = HashMap::new();
+	initial.insert([0, 0], 1);
+	layer.insert(Prefix { subweight: subweight.into_boxed_slice(), last_row: 0, state: start }, initial);
+
+	// `position` letters have been placed, so an ascent from the last one adds `position` to maj
+	for position in 1..size {
+		let mut next_layer: HashMap<Prefix<S>, Distribution> = HashMap::new();
+
+		for (prefix, distribution) in layer {
+			for row in 0..weight.len() {
+				let column = prefix.subweight[row];
+				if column == weight[row] || (row > 0 && column == prefix.subweight[row - 1]) {
+					continue;
+				}
+
+				let mut subweight = prefix.subweight.clone();
+				subweight[row] += 1;
+				let state = step(&prefix.state, row, column + 1);
+				let next = Prefix { subweight: subweight, last_row: row, state: state };
+
+				let (maj, ascent) = if row > prefix.last_row { (position, 1) } else { (0, 0) };
+				add_distribution(next_layer.entry(next).or_insert_with(HashMap::new), &distribution, maj, ascent)?;
+			}
+		}
+
+		layer = next_layer;
+
+		if !progress(position + 1) {
+			return Err("interrupted");
+		}
+	}
+
+	let mut totals = HashMap::new();
+	for (prefix, distribution) in layer {
+		add_distribution(&mut totals, &distribution, 0, bonus(&prefix.state))?;
+	}
+	Ok(histogram_polynomial(totals))
+}
+
+/// The joint distribution of the major index and the number of descents of the standard tableaux
+/// of the given shape, where tableau descents are the ascents of lattice words. `progress` is
+/// called with the number of letters placed so far, and returning false from it stops the
+/// computation with "interrupted".
+pub(crate) fn maj_des_polynomial<L, P>(weight: &Partition<L>, progress: P) -> Result<Polynomial, &'static str>
+	where L: Letter,
+	      P: FnMut(usize) -> bool
+{
+	joint_distribution(&**weight, (), |_, _, _| (), |_| 0, progress)
+}
+
+/// The joint distribution of the major index and the number of cyclic descents for a rectangle,
+/// tracking the hole of `TableauCyclicDescentIter` instead of the whole tableau.
+pub(crate) fn maj_cdes_polynomial<L, P>(weight: &Partition<L>, progress: P) -> Result<Polynomial, &'static str>
+	where L: Letter,
+	      P: FnMut(usize) -> bool
+{
+	if !weight.is_rectangle() {
+		return Err("only implemented for rectangular shapes");
+	}
+
+	let start = Hole { row: 0, column: 1, cyclic_descent: false };
+	joint_distribution(
+		&**weight,
+		start,
+		|hole, row, column| if row == hole.row {
+			Hole { row: hole.row, column: hole.column + 1, cyclic_descent: false }
+		} else if column == hole.column {
+			Hole { row: hole.row + 1, column: hole.column, cyclic_descent: true }
+		} else {
+			*hole
+		},
+		|hole| if hole.cyclic_descent { 1 } else { 0 },
+		progress,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use counting::hook_length_count;
+	use lattice_word::LatticeWord;
+	use lattice_words::LatticeWords;
+
+	#[test]
+	fn maj_des() {
+		let weights = vec![vec![], vec![1], vec![4], vec![3, 2], vec![4, 3, 3, 1], vec![2, 2, 2, 1, 1], vec![5, 3, 1]];
+		for weight in weights {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+			assert_eq!(
+				maj_des_polynomial(lattice_words.shape(), |_| true),
+				Ok(lattice_words.generating_function(|word| [word.major_index(), word.ascents().count()]))
+			);
+		}
+	}
+
+	#[test]
+	fn maj_cdes() {
+		let weights = vec![vec![3], vec![2, 2], vec![3, 3], vec![4, 4, 4], vec![3, 3, 3, 3], vec![2, 2, 2, 2, 2]];
+		for weight in weights {
+			let lattice_words = LatticeWords::new(weight).unwrap();
+			assert_eq!(
+				maj_cdes_polynomial(lattice_words.shape(), |_| true),
+				Ok(lattice_words.generating_function(|word: LatticeWord<&[u8]>| {
+					[word.major_index(), word.tableau_cyclic_descents().unwrap().count()]
+				}))
+			);
+		}
+
+		assert!(maj_cdes_polynomial(LatticeWords::new(vec![3, 2]).unwrap().shape(), |_| true).is_err());
+	}
+
+	#[test]
+	fn progress() {
+		let shape = Partition::new(vec![4, 3, 1]).unwrap();
+
+		let mut placed = Vec::new();
+		assert!(maj_des_polynomial(&shape, |letters| {
+			placed.push(letters);
+			true
+		}).is_ok());
+		assert_eq!(placed, (2..9).collect::<Vec<_>>());
+
+		assert_eq!(maj_des_polynomial(&shape, |letters| letters < 5), Err("interrupted"));
+	}
+
+	#[test]
+	fn beyond_enumeration() {
+		// 701149020 standard tableaux, many more than the tests can visit one by one
+		let lattice_words = LatticeWords::new(vec![5; 5]).unwrap();
+		let count = hook_length_count(lattice_words.weight()).unwrap();
+
+		let shape = lattice_words.shape();
+		for polynomial in vec![maj_des_polynomial(shape, |_| true), maj_cdes_polynomial(shape, |_| true)] {
+			let total = polynomial.unwrap().terms().fold(0, |partial, (_, coefficient)| partial + coefficient);
+			assert_eq!(total as usize, count);
+		}
+	}
+}